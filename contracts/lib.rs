@@ -13,37 +13,137 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with provider.  If not, see <http://www.gnu.org/licenses/>.
-#![cfg_attr(not(feature = "std"), no_std)]
-
-use ink_lang as ink;
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 #[ink::contract]
 pub mod dapp {
     use prosopo::ProsopoRef;
-    use ink_storage::{
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::{
+        call::{
+            build_call,
+            ExecutionInput,
+            FromAccountId,
+            Selector,
+        },
+        DefaultEnvironment,
+    };
+    use ink::prelude::{
+        string::String,
+        vec,
+        vec::Vec,
+    };
+    use ink::storage::{
+        Lazy,
         Mapping,
-        traits::SpreadAllocate,
     };
 
     #[ink(storage)]
-    #[derive(SpreadAllocate)]
     pub struct Dapp {
         /// Total token supply.
         total_supply: Balance,
         /// Mapping from owner to number of owned token.
         balances: Mapping<AccountId, Balance>,
+        /// Mapping of the token amount which an account is allowed to withdraw
+        /// from another account.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
         /// Amount of tokens to drip feed via the faucet function
         faucet_amount: Balance,
         /// Token holder who initially receives all tokens
         token_holder: AccountId,
         /// The percentage of correct captchas that an Account must have answered correctly
         human_threshold: u8,
-        /// The time in ms within which a user must have answered a captcha
-        recency_threshold: u32,
-        /// The address of the prosopo bot protection contract
-        prosopo_account: AccountId
+        /// How recently a user must have answered a captcha
+        recency_policy: RecencyPolicy,
+        /// The address of the prosopo bot protection contract the contract was created with.
+        /// Only read to seed the verifier registry when migrating storage from version 1.
+        prosopo_account: AccountId,
+        /// Optional name of the token
+        token_name: Lazy<String>,
+        /// Optional symbol of the token
+        token_symbol: Lazy<String>,
+        /// Number of decimals used to display the token
+        token_decimals: u8,
+        /// The time in ms an account must wait between two faucet claims
+        faucet_cooldown: Timestamp,
+        /// Optional maximum amount of tokens an account can claim from the faucet in total
+        faucet_lifetime_cap: Option<Balance>,
+        /// Mapping from account to the timestamp of its last faucet claim
+        last_claim: Mapping<AccountId, Timestamp>,
+        /// Mapping from account to the total amount it has claimed from the faucet
+        claimed: Mapping<AccountId, Balance>,
+        /// Whether the faucet only pays out to the caller or one of the accounts it relays for
+        faucet_caller_only: bool,
+        /// Accounts allowed to submit faucet claims on behalf of other accounts
+        relayers: Mapping<AccountId, bool>,
+        /// Account allowed to change the contract parameters
+        owner: AccountId,
+        /// Optional amount above which the sender of a transfer must pass the humanity check
+        large_transfer_threshold: Option<Balance>,
+        /// Optional amount of native currency to drip feed via the native faucet function
+        native_faucet_amount: Option<Balance>,
+        /// The time in ms an account must wait between two native faucet claims
+        native_faucet_cooldown: Timestamp,
+        /// Mapping from account to the timestamp of its last native faucet claim
+        native_last_claim: Mapping<AccountId, Timestamp>,
+        /// Amount of native currency set aside to pay out native faucet claims
+        native_faucet_reserve: Balance,
+        /// Optional time in ms for which a successful humanity check of an account is reused
+        verification_cache_ttl: Option<Timestamp>,
+        /// Mapping from account to the timestamp of its last successful humanity check
+        verified_at: Mapping<AccountId, Timestamp>,
+        /// Payout tiers ordered by ascending human score threshold
+        faucet_tiers: Lazy<Vec<FaucetTier>>,
+        /// Optional account allowed to pause the contract alongside the owner
+        guardian: Option<AccountId>,
+        /// Whether `faucet` and `native_faucet` are paused
+        faucet_paused: bool,
+        /// Whether token transfers and burns are paused
+        transfers_paused: bool,
+        /// Whether allowance based flows are paused
+        allowances_paused: bool,
+        /// Version of the storage layout the stored data was written with
+        storage_version: Lazy<u32>,
+        /// Contracts consulted to decide whether an account is human
+        verifiers: Lazy<Vec<Verifier>>,
+        /// How the results of the verifiers are combined
+        verification_policy: Lazy<VerificationPolicy>,
+        /// Ordered fallback addresses tried when a call to a `Prosopo` verifier fails
+        prosopo_fallbacks: Mapping<AccountId, Vec<AccountId>>,
+        /// Accounts that pass the faucet humanity check without querying the verifiers
+        allowlisted: Mapping<AccountId, ()>,
+        /// Accounts that always fail the faucet humanity check
+        denylisted: Mapping<AccountId, ()>,
+        /// Counter bumped whenever a parameter of the humanity check changes
+        verification_epoch: Lazy<u32>,
+        /// Mapping from account to the verification epoch of its cached humanity check
+        verified_epoch: Mapping<AccountId, u32>,
+        /// Mapping from account to the `Prosopo` contract that answered its cached humanity check
+        verified_instance: Mapping<AccountId, AccountId>,
     }
 
+    /// Version of the storage layout written by this code.
+    ///
+    /// Bump it whenever the layout changes and add the matching step to `migrate`. Fields added
+    /// by later versions should live in `Lazy` or `Mapping` cells so that the storage written by
+    /// an older version still loads before `migrate` has run.
+    const STORAGE_VERSION: u32 = 2;
+
+    /// Maximum number of registered verifiers, bounding the cross-contract calls per check.
+    const MAX_VERIFIERS: usize = 8;
+
+    /// Selector of the `is_allowed(AccountId) -> bool` message of allowlist verifiers.
+    const IS_ALLOWED_SELECTOR: [u8; 4] = ink::selector_bytes!("is_allowed");
+
+    /// Selector of the `has_attestation(AccountId) -> bool` message of attestation verifiers.
+    const HAS_ATTESTATION_SELECTOR: [u8; 4] = ink::selector_bytes!("has_attestation");
+
+    /// Maximum number of addresses, primary included, tried for a `Prosopo` verifier.
+    const MAX_PROSOPO_ACCOUNTS: usize = 4;
+
+    /// Maximum number of faucet payout tiers, bounding the humanity queries per faucet claim.
+    const MAX_FAUCET_TIERS: usize = 8;
+
     /// Event emitted when a token transfer occurs.
     #[ink(event)]
     pub struct Transfer {
@@ -54,128 +154,2867 @@ pub mod dapp {
         value: Balance,
     }
 
-    /// Error types.
+    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
+    /// up to the amount of `value` tokens from `owner`.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when an account claims tokens from the faucet.
+    #[ink(event)]
+    pub struct FaucetClaimed {
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+        threshold_used: u8,
+        tier: Option<u8>,
+        /// The `Prosopo` contract that answered the humanity check, `None` if no `Prosopo`
+        /// contract was queried for it
+        prosopo_instance: Option<AccountId>,
+    }
+
+    /// Event emitted when an account claims native currency from the native faucet.
+    #[ink(event)]
+    pub struct NativeFaucetClaimed {
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when an account fails the humanity check of the faucet.
+    ///
+    /// The faucet call still succeeds with `FaucetOutcome::HumanCheckFailed`, so the event is
+    /// recorded on-chain.
+    #[ink(event)]
+    pub struct HumanCheckFailed {
+        #[ink(topic)]
+        account: AccountId,
+        reason: Error,
+    }
+
+    /// Event emitted when a part of the contract is paused.
+    #[ink(event)]
+    pub struct Paused {
+        target: PauseTarget,
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    /// Event emitted when a part of the contract is unpaused.
+    #[ink(event)]
+    pub struct Unpaused {
+        target: PauseTarget,
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    /// Event emitted when an account is added to an access list.
+    #[ink(event)]
+    pub struct AccountListed {
+        list: AccessList,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when an account is removed from an access list.
+    #[ink(event)]
+    pub struct AccountUnlisted {
+        list: AccessList,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when the code of the contract is replaced.
+    #[ink(event)]
+    pub struct Upgraded {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    /// Event emitted when the storage is migrated to a newer layout version.
+    #[ink(event)]
+    pub struct Migrated {
+        from_version: u32,
+        to_version: u32,
+    }
+
+    /// Event emitted when the ownership of the contract is transferred.
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    /// Event emitted when the owner changes a contract parameter.
+    #[ink(event)]
+    pub struct ConfigChanged {
+        parameter: ConfigParameter,
+    }
+
+    /// How recently a user must have answered a captcha correctly.
+    ///
+    /// `Prosopo` only reports the age of the last correct captcha in ms derived from block
+    /// timestamps and exposes no block number, so the window can only be given in ms.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum RecencyPolicy {
+        /// The last correct captcha must have been answered within this many ms.
+        Milliseconds(u64),
+    }
+
+    impl RecencyPolicy {
+        /// Returns the recency window in ms.
+        pub fn window_ms(&self) -> u64 {
+            match *self {
+                RecencyPolicy::Milliseconds(window_ms) => window_ms,
+            }
+        }
+    }
+
+    /// The kind of contract a verifier is, deciding how it is queried.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum VerifierKind {
+        /// A `Prosopo` protocol contract, checked against the human and recency thresholds
+        Prosopo,
+        /// An allowlist contract exposing `is_allowed(AccountId) -> bool`
+        Allowlist,
+        /// An attestation registry exposing `has_attestation(AccountId) -> bool`
+        Attestation,
+    }
+
+    /// A contract registered to take part in the humanity check.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Verifier {
+        pub contract: AccountId,
+        pub kind: VerifierKind,
+        /// Weight of a passing result under `VerificationPolicy::Weighted`
+        pub weight: u32,
+    }
+
+    /// How the results of the registered verifiers are combined.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum VerificationPolicy {
+        /// At least one verifier must pass
+        AnyOf,
+        /// Every verifier must pass
+        AllOf,
+        /// The weights of the passing verifiers must add up to at least `min_weight`
+        Weighted { min_weight: u32 },
+    }
+
+    /// A faucet payout tier paying `amount` to accounts with a human score of at least
+    /// `threshold` percent.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct FaucetTier {
+        pub threshold: u8,
+        pub amount: Balance,
+    }
+
+    /// The faucet payout an account qualifies for.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct FaucetPayout {
+        /// Index of the tier the payout comes from, `None` for the base faucet amount
+        pub tier: Option<u8>,
+        /// The human score threshold the account met
+        pub threshold: u8,
+        /// The amount of tokens paid out
+        pub amount: Balance,
+    }
+
+    /// A part of the contract that can be paused independently.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PauseTarget {
+        /// `faucet` and `native_faucet`
+        Faucet,
+        /// `transfer`, `transfer_from`, `burn` and `burn_from`
+        Transfers,
+        /// `approve`, `increase_allowance`, `decrease_allowance`, `transfer_from` and `burn_from`
+        Allowances,
+    }
+
+    /// An owner-managed list of accounts overriding the faucet humanity check.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum AccessList {
+        /// Accounts that pass the humanity check without querying the verifiers
+        Allowlist,
+        /// Accounts that fail the humanity check, even if allowlisted
+        Denylist,
+    }
+
+    /// The result of a faucet claim that did not revert.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum FaucetOutcome {
+        /// The account was paid out
+        Claimed,
+        /// The account failed the humanity check with the error and nothing was paid out
+        HumanCheckFailed(Error),
+    }
+
+    /// How an account passed the humanity check.
+    enum HumanCheck {
+        /// Decided by the access lists without querying the verifiers
+        Listed,
+        /// Served from the verification cache, along with the `Prosopo` contract that answered
+        /// the cached check, if any
+        Cached(Option<AccountId>),
+        /// Decided by the verifiers, along with the `Prosopo` contract that answered, if any
+        Verified(Option<AccountId>),
+    }
+
+    impl HumanCheck {
+        /// Returns the `Prosopo` contract that answered the check, if any.
+        fn prosopo_instance(&self) -> Option<AccountId> {
+            match *self {
+                HumanCheck::Listed => None,
+                HumanCheck::Cached(prosopo_instance) | HumanCheck::Verified(prosopo_instance) => prosopo_instance,
+            }
+        }
+    }
+
+    /// Whether an account can currently claim from the faucet.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum FaucetStatus {
+        /// The account would receive the payout
+        Eligible(FaucetPayout),
+        /// The faucet would reject the account with the error
+        Ineligible(Error),
+    }
+
+    /// Every contract parameter, as returned by `config`.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct DappConfig {
+        pub owner: AccountId,
+        pub guardian: Option<AccountId>,
+        pub token_holder: AccountId,
+        pub verifiers: Vec<Verifier>,
+        pub verification_policy: VerificationPolicy,
+        pub prosopo_accounts: Vec<AccountId>,
+        pub faucet_amount: Balance,
+        pub human_threshold: u8,
+        pub recency_policy: RecencyPolicy,
+        pub faucet_cooldown: Timestamp,
+        pub faucet_lifetime_cap: Option<Balance>,
+        pub faucet_caller_only: bool,
+        pub faucet_tiers: Vec<FaucetTier>,
+        pub large_transfer_threshold: Option<Balance>,
+        pub native_faucet_amount: Option<Balance>,
+        pub native_faucet_cooldown: Timestamp,
+        pub verification_cache_ttl: Option<Timestamp>,
+    }
+
+    /// A contract parameter along with its new value.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ConfigParameter {
+        FaucetAmount(Balance),
+        HumanThreshold(u8),
+        RecencyPolicy(RecencyPolicy),
+        Verifiers(Vec<Verifier>),
+        VerificationPolicy(VerificationPolicy),
+        ProsopoAccounts(Vec<AccountId>),
+        FaucetCooldown(Timestamp),
+        FaucetLifetimeCap(Option<Balance>),
+        FaucetCallerOnly(bool),
+        Relayer(AccountId, bool),
+        LargeTransferThreshold(Option<Balance>),
+        NativeFaucetAmount(Option<Balance>),
+        NativeFaucetCooldown(Timestamp),
+        VerificationCacheTtl(Option<Timestamp>),
+        FaucetTiers(Vec<FaucetTier>),
+        Guardian(Option<AccountId>),
+    }
+
+    /// Error types.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
         /// Returned if not enough balance to fulfill a request is available.
         InsufficientBalance,
+        /// Returned if not enough allowance to fulfill a request is available.
+        InsufficientAllowance,
         /// Returned if the user has not completed a captcha
-        UserNotHuman
+        UserNotHuman,
+        /// Returned if the call to the `Prosopo` contract failed
+        ProsopoCallFailed,
+        /// Returned if the `Prosopo` contract has no captcha history for the user
+        NoCaptchaHistory,
+        /// Returned if the user's last correct captcha is older than the recency threshold
+        CaptchaTooOld,
+        /// Returned if the account claimed from the faucet less than the cooldown period ago
+        FaucetCooldownActive { remaining_ms: Timestamp },
+        /// Returned if the account has reached its lifetime faucet claim cap
+        FaucetCapReached,
+        /// Returned if the caller may not claim from the faucet on behalf of the account
+        FaucetCallerNotAllowed,
+        /// Returned if the caller is not the owner of the contract
+        NotOwner,
+        /// Returned if the faucet reserve cannot cover a faucet claim
+        FaucetEmpty,
+        /// Returned if a balance or supply update would overflow
+        Overflow,
+        /// Returned if no native faucet amount is configured
+        NativeFaucetDisabled,
+        /// Returned if the native faucet reserve cannot cover a native faucet claim
+        NativeFaucetEmpty,
+        /// Returned if the transfer of native currency failed
+        NativeTransferFailed,
+        /// Returned if the faucet tiers are not ordered by strictly ascending thresholds of at
+        /// most 100 percent or exceed the maximum number of tiers
+        InvalidFaucetTiers,
+        /// Returned if the requested part of the contract is paused
+        Paused,
+        /// Returned if the caller is neither the owner nor the guardian of the contract
+        NotGuardian,
+        /// Returned if the code of the contract could not be replaced
+        UpgradeFailed,
+        /// Returned if the storage is already at the current layout version
+        NothingToMigrate,
+        /// Returned if the call to a verifier other than `Prosopo` returned an error
+        VerifierCallFailed,
+        /// Returned if no verifier is registered
+        NoVerifiers,
+        /// Returned if the maximum number of verifiers is already registered
+        TooManyVerifiers,
+        /// Returned if a list of `Prosopo` addresses is empty or too long
+        InvalidProsopoAccounts,
+        /// Returned if the account is on the faucet denylist
+        Denylisted,
+        /// Returned if a verifier has a weight of zero or its contract is already registered
+        InvalidVerifier,
+        /// Returned if a weighted policy requires more weight than the verifiers have in total
+        InvalidVerificationPolicy,
+    }
+
+    impl Error {
+        /// Returns `true` if the error reports that the humanity check could not be carried out,
+        /// rather than that the account failed it.
+        pub fn is_call_failure(&self) -> bool {
+            matches!(self, Error::ProsopoCallFailed | Error::VerifierCallFailed | Error::NoVerifiers)
+        }
+    }
+
+    /// Answers the queries the humanity check makes to the registered verifier contracts.
+    pub trait HumanityBackend {
+        /// Returns the time in ms since `accountid` last answered a captcha correctly according
+        /// to the `Prosopo` contract at `prosopo_account`.
+        fn last_correct_captcha_ms(&self, prosopo_account: AccountId, accountid: AccountId) -> Result<u32, Error>;
+
+        /// Returns `true` if `accountid` answered at least `threshold` percent of its captchas
+        /// correctly according to the `Prosopo` contract at `prosopo_account`.
+        fn is_human_user(&self, prosopo_account: AccountId, accountid: AccountId, threshold: u8) -> Result<bool, Error>;
+
+        /// Calls the `AccountId -> bool` message with `selector` on `contract` for `accountid`.
+        fn query_verifier(&self, contract: AccountId, selector: [u8; 4], accountid: AccountId) -> Result<bool, Error>;
+    }
+
+    /// Humanity backend querying the deployed verifier contracts.
+    pub struct ContractBackend;
+
+    impl HumanityBackend for ContractBackend {
+        fn last_correct_captcha_ms(&self, prosopo_account: AccountId, accountid: AccountId) -> Result<u32, Error> {
+            let instance: ProsopoRef = FromAccountId::from_account_id(prosopo_account);
+            match instance.call().dapp_operator_last_correct_captcha(accountid).try_invoke() {
+                Ok(Ok(Ok(last_correct_captcha))) => Ok(last_correct_captcha.before_ms),
+                Ok(Ok(Err(_))) => Err(Error::NoCaptchaHistory),
+                _ => Err(Error::ProsopoCallFailed),
+            }
+        }
+
+        fn is_human_user(&self, prosopo_account: AccountId, accountid: AccountId, threshold: u8) -> Result<bool, Error> {
+            let instance: ProsopoRef = FromAccountId::from_account_id(prosopo_account);
+            match instance.call().dapp_operator_is_human_user(accountid, threshold).try_invoke() {
+                Ok(Ok(Ok(is_human))) => Ok(is_human),
+                Ok(Ok(Err(_))) => Err(Error::NoCaptchaHistory),
+                _ => Err(Error::ProsopoCallFailed),
+            }
+        }
+
+        fn query_verifier(&self, contract: AccountId, selector: [u8; 4], accountid: AccountId) -> Result<bool, Error> {
+            match build_call::<DefaultEnvironment>()
+                .call(contract)
+                .exec_input(ExecutionInput::new(Selector::new(selector)).push_arg(accountid))
+                .returns::<bool>()
+                .try_invoke()
+            {
+                Ok(Ok(is_verified)) => Ok(is_verified),
+                _ => Err(Error::VerifierCallFailed),
+            }
+        }
     }
 
     impl Dapp {
         /// Creates a new contract with the specified initial supply and loads an instance of the
         /// `prosopo` contract
         #[ink(constructor, payable)]
-        pub fn new(initial_supply: Balance, faucet_amount: Balance, prosopo_account: AccountId, human_threshold: u8, recency_threshold: u32) -> Self {
-            ink_lang::codegen::initialize_contract(|contract| Self::new_init(contract, initial_supply, faucet_amount, prosopo_account, human_threshold, recency_threshold))
-        }
-
-        /// Default initializes the ERC-20 contract with the specified initial supply.
-        fn new_init(&mut self, initial_supply: Balance, faucet_amount: Balance, prosopo_account: AccountId, human_threshold: u8, recency_threshold: u32) {
+        #[allow(clippy::too_many_arguments)]
+        pub fn new(initial_supply: Balance, faucet_amount: Balance, prosopo_account: AccountId, human_threshold: u8, recency_policy: RecencyPolicy, token_name: Option<String>, token_symbol: Option<String>, token_decimals: u8, faucet_cooldown: Timestamp, faucet_lifetime_cap: Option<Balance>, faucet_caller_only: bool, relayers: Vec<AccountId>) -> Self {
             let caller = Self::env().caller();
-            self.balances.insert(&caller, &initial_supply);
-            self.total_supply = initial_supply;
-            self.faucet_amount = faucet_amount;
-            self.token_holder = caller;
-            self.human_threshold = human_threshold;
-            self.recency_threshold = recency_threshold;
-            self.prosopo_account = prosopo_account;
-            // Events not working due to bug https://github.com/paritytech/ink/issues/1000
-            // self.env().emit_event(Transfer {
-            //     from: None,
-            //     to: Some(caller),
-            //     value: initial_supply,
-            // });
+            let mut balances = Mapping::default();
+            balances.insert(caller, &initial_supply);
+            let mut relayer_accounts = Mapping::default();
+            for relayer in relayers.iter() {
+                relayer_accounts.insert(relayer, &true);
+            }
+            let mut name = Lazy::new();
+            if let Some(token_name) = token_name {
+                name.set(&token_name);
+            }
+            let mut symbol = Lazy::new();
+            if let Some(token_symbol) = token_symbol {
+                symbol.set(&token_symbol);
+            }
+            let mut storage_version = Lazy::new();
+            storage_version.set(&STORAGE_VERSION);
+            let mut verifiers = Lazy::new();
+            verifiers.set(&vec![Verifier {
+                contract: prosopo_account,
+                kind: VerifierKind::Prosopo,
+                weight: 1,
+            }]);
+            let mut verification_policy = Lazy::new();
+            verification_policy.set(&VerificationPolicy::AnyOf);
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value: initial_supply,
+            });
+            Self {
+                total_supply: initial_supply,
+                balances,
+                allowances: Mapping::default(),
+                faucet_amount,
+                token_holder: caller,
+                human_threshold,
+                recency_policy,
+                prosopo_account,
+                token_name: name,
+                token_symbol: symbol,
+                token_decimals,
+                faucet_cooldown,
+                faucet_lifetime_cap,
+                last_claim: Mapping::default(),
+                claimed: Mapping::default(),
+                faucet_caller_only,
+                relayers: relayer_accounts,
+                owner: caller,
+                large_transfer_threshold: None,
+                native_faucet_amount: None,
+                native_faucet_cooldown: faucet_cooldown,
+                native_last_claim: Mapping::default(),
+                native_faucet_reserve: 0,
+                verification_cache_ttl: None,
+                verified_at: Mapping::default(),
+                faucet_tiers: Lazy::new(),
+                guardian: None,
+                faucet_paused: false,
+                transfers_paused: false,
+                allowances_paused: false,
+                storage_version,
+                verifiers,
+                verification_policy,
+                prosopo_fallbacks: Mapping::default(),
+                allowlisted: Mapping::default(),
+                denylisted: Mapping::default(),
+                verification_epoch: Lazy::new(),
+                verified_epoch: Mapping::default(),
+                verified_instance: Mapping::default(),
+            }
         }
 
         /// Faucet function for sending tokens to humans
+        ///
+        /// Tokens are paid out of the faucet reserve held by the contract itself. The amount is
+        /// taken from the highest faucet tier the account's human score meets, falling back to
+        /// `faucet_amount`.
+        ///
+        /// If the faucet is restricted to callers, `accountid` must be the caller unless the
+        /// caller is a relayer.
+        ///
+        /// # Errors
+        ///
+        /// Returns `FaucetCallerNotAllowed` if the caller may not claim for `accountid`.
+        ///
+        /// Returns `FaucetCooldownActive` if `accountid` claimed less than the cooldown period
+        /// ago and `FaucetCapReached` if the claim would exceed the lifetime cap.
+        ///
+        /// Returns `FaucetEmpty` if the faucet reserve cannot cover the claim.
+        ///
+        /// If `accountid` does not pass the humanity check, a `HumanCheckFailed` event is emitted
+        /// and `FaucetOutcome::HumanCheckFailed` is returned without reverting, so that failed
+        /// verifications stay visible on-chain. Denylisted accounts always fail it with
+        /// `Denylisted` and allowlisted accounts skip it.
+        ///
+        /// Returns `ProsopoCallFailed`, `VerifierCallFailed` or `NoVerifiers` if the humanity
+        /// check could not be carried out.
         #[ink(message)]
-        pub fn faucet(&mut self, accountid: AccountId)-> Result<(), Error>  {
-            let token_holder = self.token_holder;
-            if self.is_human(accountid, self.human_threshold, self.recency_threshold) {
-                self.transfer_from_to(&token_holder, &accountid, self.faucet_amount);
-            } else {
-                return Err(Error::UserNotHuman);
+        pub fn faucet(&mut self, accountid: AccountId)-> Result<FaucetOutcome, Error>  {
+            self.ensure_not_paused(PauseTarget::Faucet)?;
+            let backend = self.humanity_backend();
+            self.faucet_with(&backend, accountid)
+        }
+
+        /// Pays out the faucet to `accountid` using `backend` for the humanity check.
+        fn faucet_with<B: HumanityBackend>(&mut self, backend: &B, accountid: AccountId) -> Result<FaucetOutcome, Error> {
+            let reserve = self.env().account_id();
+            let now = self.env().block_timestamp();
+            self.ensure_faucet_caller(&accountid)?;
+            Self::ensure_cooldown(self.last_claim.get(accountid), self.faucet_cooldown, now)?;
+            let prosopo_instance = match self.ensure_faucet_human(backend, accountid) {
+                Ok(prosopo_instance) => prosopo_instance,
+                Err(reason) if reason.is_call_failure() => return Err(reason),
+                Err(reason) => return Ok(FaucetOutcome::HumanCheckFailed(reason)),
+            };
+            let payout = self.faucet_payout(backend, accountid, prosopo_instance);
+            let claimed = self.ensure_faucet_funds(&accountid, payout.amount)?;
+            self.transfer_from_to(&reserve, &accountid, payout.amount)?;
+            self.last_claim.insert(&accountid, &now);
+            self.claimed.insert(&accountid, &claimed);
+            self.env().emit_event(FaucetClaimed {
+                account: accountid,
+                amount: payout.amount,
+                threshold_used: payout.threshold,
+                tier: payout.tier,
+                prosopo_instance,
+            });
+            Ok(FaucetOutcome::Claimed)
+        }
+
+        /// Reports whether `accountid` could claim from the faucet right now and, if not, why.
+        ///
+        /// Runs the same checks as `faucet` except for the caller check, without changing state.
+        #[ink(message)]
+        pub fn faucet_status(&self, accountid: AccountId) -> FaucetStatus {
+            let backend = self.humanity_backend();
+            let now = self.env().block_timestamp();
+            let status = self.ensure_not_paused(PauseTarget::Faucet)
+                .and_then(|()| Self::ensure_cooldown(self.last_claim.get(accountid), self.faucet_cooldown, now))
+                .and_then(|()| self.check_faucet_human(&backend, accountid, now))
+                .map(|check| self.faucet_payout(&backend, accountid, check.prosopo_instance()))
+                .and_then(|payout| self.ensure_faucet_funds(&accountid, payout.amount).map(|_| payout));
+            match status {
+                Ok(payout) => FaucetStatus::Eligible(payout),
+                Err(reason) => FaucetStatus::Ineligible(reason),
             }
-            Ok(())
         }
 
-        /// Calls the `Prosopo` contract to check if `accountid` is human
+        /// Returns the faucet payout `accountid` currently qualifies for.
+        ///
+        /// # Errors
+        ///
+        /// Returns the error reported by the humanity check if `accountid` does not pass it.
         #[ink(message)]
-        pub fn is_human(&self, accountid: AccountId, threshold: u8, recency: u32) -> bool {
-            let prosopo_instance: ProsopoRef = ink_env::call::FromAccountId::from_account_id(self.prosopo_account);
-            prosopo_instance.dapp_operator_is_human_user(accountid, threshold).unwrap();
-            // check that the captcha was completed within the last X seconds
-            let last_correct_captcha = prosopo_instance.dapp_operator_last_correct_captcha(accountid).unwrap();
-            return last_correct_captcha.before_ms <= recency && prosopo_instance.dapp_operator_is_human_user(accountid, threshold).unwrap()
+        pub fn preview_faucet(&self, accountid: AccountId) -> Result<FaucetPayout, Error> {
+            let backend = self.humanity_backend();
+            let check = self.check_faucet_human(&backend, accountid, self.env().block_timestamp())?;
+            Ok(self.faucet_payout(&backend, accountid, check.prosopo_instance()))
         }
 
-        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        /// Picks the highest faucet tier whose threshold `accountid` meets, falling back to
+        /// `faucet_amount`.
         ///
-        /// On success a `Transfer` event is emitted.
+        /// Expects `accountid` to have passed the humanity check, answered by `prosopo_instance`
+        /// with `human_threshold`, possibly from the verification cache. Tiers at or below it are
+        /// therefore met without a query and higher ones are looked up on the same instance.
+        /// Without an instance, e.g. for an allowlisted account, every tier is looked up on the
+        /// first registered `Prosopo` verifier or its fallbacks. A lookup that cannot be answered
+        /// counts as not meeting the tier.
+        fn faucet_payout<B: HumanityBackend>(&self, backend: &B, accountid: AccountId, prosopo_instance: Option<AccountId>) -> FaucetPayout {
+            let tiers = self.faucet_tiers.get().unwrap_or_default();
+            for (index, tier) in tiers.iter().enumerate().rev() {
+                let threshold = match prosopo_instance {
+                    Some(_) if tier.threshold <= self.human_threshold => self.human_threshold,
+                    Some(instance) if backend.is_human_user(instance, accountid, tier.threshold) == Ok(true) => tier.threshold,
+                    None if self.meets_threshold_on_primary(backend, accountid, tier.threshold) => tier.threshold,
+                    _ => continue,
+                };
+                return FaucetPayout {
+                    tier: Some(index as u8),
+                    threshold,
+                    amount: tier.amount,
+                };
+            }
+            FaucetPayout {
+                tier: None,
+                threshold: self.human_threshold,
+                amount: self.faucet_amount,
+            }
+        }
+
+        /// Returns `true` if the first registered `Prosopo` verifier or its fallbacks report that
+        /// `accountid` meets `threshold`.
+        fn meets_threshold_on_primary<B: HumanityBackend>(&self, backend: &B, accountid: AccountId, threshold: u8) -> bool {
+            let is_human = self.primary_prosopo().map(|primary| {
+                self.with_prosopo_failover(primary, |instance| backend.is_human_user(instance, accountid, threshold))
+            });
+            matches!(is_human, Some(Ok((true, _))))
+        }
+
+        /// Returns the faucet payout tiers ordered by ascending threshold.
+        #[ink(message)]
+        pub fn faucet_tiers(&self) -> Vec<FaucetTier> {
+            self.faucet_tiers.get().unwrap_or_default()
+        }
+
+        /// Native faucet function for sending native currency to humans
+        ///
+        /// Native currency is paid out of the native faucet reserve. The same caller and humanity
+        /// rules as for `faucet` apply, with a separate cooldown.
         ///
         /// # Errors
         ///
-        /// Returns `InsufficientBalance` error if there are not enough tokens on
-        /// the caller's account balance.
+        /// Returns `NativeFaucetDisabled` if no native faucet amount is configured.
+        ///
+        /// Returns `FaucetCallerNotAllowed` if the caller may not claim for `accountid`.
+        ///
+        /// Returns `FaucetCooldownActive` if `accountid` claimed less than the native cooldown
+        /// period ago.
+        ///
+        /// Returns `NativeFaucetEmpty` if the native faucet reserve cannot cover the claim.
+        ///
+        /// A failed humanity check is reported as for `faucet`.
         #[ink(message)]
-        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), Error> {
-            let from = self.env().caller();
-            self.transfer_from_to(&from, &to, value)
+        pub fn native_faucet(&mut self, accountid: AccountId) -> Result<FaucetOutcome, Error> {
+            self.ensure_not_paused(PauseTarget::Faucet)?;
+            let backend = self.humanity_backend();
+            self.native_faucet_with(&backend, accountid)
         }
 
-        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        /// Pays out the native faucet to `accountid` using `backend` for the humanity check.
+        fn native_faucet_with<B: HumanityBackend>(&mut self, backend: &B, accountid: AccountId) -> Result<FaucetOutcome, Error> {
+            let amount = self.native_faucet_amount.ok_or(Error::NativeFaucetDisabled)?;
+            let now = self.env().block_timestamp();
+            self.ensure_faucet_caller(&accountid)?;
+            Self::ensure_cooldown(self.native_last_claim.get(accountid), self.native_faucet_cooldown, now)?;
+            let native_faucet_reserve = self.native_faucet_reserve
+                .checked_sub(amount)
+                .ok_or(Error::NativeFaucetEmpty)?;
+            match self.ensure_faucet_human(backend, accountid) {
+                Ok(_) => {}
+                Err(reason) if reason.is_call_failure() => return Err(reason),
+                Err(reason) => return Ok(FaucetOutcome::HumanCheckFailed(reason)),
+            }
+            self.native_faucet_reserve = native_faucet_reserve;
+            self.native_last_claim.insert(accountid, &now);
+            self.env().transfer(accountid, amount).map_err(|_| Error::NativeTransferFailed)?;
+            self.env().emit_event(NativeFaucetClaimed {
+                account: accountid,
+                amount,
+            });
+            Ok(FaucetOutcome::Claimed)
+        }
+
+        /// Returns the amount of native currency set aside to pay out native faucet claims.
+        #[ink(message)]
+        pub fn native_faucet_reserve(&self) -> Balance {
+            self.native_faucet_reserve
+        }
+
+        /// Adds the transferred value to the native faucet reserve.
+        #[ink(message, payable)]
+        pub fn fund_native_faucet(&mut self) -> Result<(), Error> {
+            self.native_faucet_reserve = self.native_faucet_reserve
+                .checked_add(self.env().transferred_value())
+                .ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        /// Sends `value` native currency from the native faucet reserve to the caller.
         ///
-        /// On success a `Transfer` event is emitted.
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `NativeFaucetEmpty` error if the native faucet reserve holds less than `value`.
+        #[ink(message)]
+        pub fn withdraw_native_faucet(&mut self, value: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.native_faucet_reserve = self.native_faucet_reserve
+                .checked_sub(value)
+                .ok_or(Error::NativeFaucetEmpty)?;
+            self.env().transfer(self.env().caller(), value).map_err(|_| Error::NativeTransferFailed)
+        }
+
+        /// Returns the timestamp of the last native faucet claim of `accountid`, if any.
+        #[ink(message)]
+        pub fn native_last_claim(&self, accountid: AccountId) -> Option<Timestamp> {
+            self.native_last_claim.get(accountid)
+        }
+
+        /// Returns the amount of tokens held by the contract to pay out faucet claims.
+        #[ink(message)]
+        pub fn faucet_reserve(&self) -> Balance {
+            self.balance_of_impl(&self.env().account_id())
+        }
+
+        /// Moves `value` tokens from the caller's account into the faucet reserve.
         ///
         /// # Errors
         ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the caller's account balance.
-        fn transfer_from_to(
-            &mut self,
-            from: &AccountId,
-            to: &AccountId,
-            value: Balance,
-        ) -> Result<(), Error> {
-            let from_balance = self.balance_of_impl(from);
-            if from_balance < value {
-                return Err(Error::InsufficientBalance);
-            }
+        #[ink(message)]
+        pub fn fund_faucet(&mut self, value: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let caller = self.env().caller();
+            let reserve = self.env().account_id();
+            self.transfer_from_to(&caller, &reserve, value)
+        }
+
+        /// Moves `value` tokens from the faucet reserve into the caller's account.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `InsufficientBalance` error if the faucet reserve holds fewer than
+        /// `value` tokens.
+        #[ink(message)]
+        pub fn withdraw_faucet(&mut self, value: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let caller = self.env().caller();
+            let reserve = self.env().account_id();
+            self.transfer_from_to(&reserve, &caller, value)
+        }
+
+        /// Returns the timestamp of the last faucet claim of `accountid`, if any.
+        #[ink(message)]
+        pub fn last_claim(&self, accountid: AccountId) -> Option<Timestamp> {
+            self.last_claim.get(&accountid)
+        }
+
+        /// Returns the total amount `accountid` has claimed from the faucet.
+        #[ink(message)]
+        pub fn claimed(&self, accountid: AccountId) -> Balance {
+            self.claimed_impl(&accountid)
+        }
+
+        /// Returns `true` if `accountid` may submit faucet claims on behalf of other accounts.
+        #[ink(message)]
+        pub fn is_relayer(&self, accountid: AccountId) -> bool {
+            self.relayers.get(&accountid).unwrap_or_default()
+        }
 
-            self.balances.insert(from, &(from_balance - value));
-            let to_balance = self.balance_of_impl(to);
-            self.balances.insert(to, &(to_balance + value));
-            // Events not working due to bug https://github.com/paritytech/ink/issues/1000
-            // self.env().emit_event(Transfer {
-            //     from: Some(*from),
-            //     to: Some(*to),
-            //     value,
-            // });
+        /// Checks that the caller may claim from the faucet for `accountid`.
+        fn ensure_faucet_caller(&self, accountid: &AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.faucet_caller_only && caller != *accountid && !self.is_relayer(caller) {
+                return Err(Error::FaucetCallerNotAllowed);
+            }
             Ok(())
         }
 
-        /// Returns the account balance for the specified `owner`.
+        /// Checks that `accountid` passes the humanity check of the faucet.
         ///
-        /// Returns `0` if the account is non-existent.
+        /// The access lists are consulted first. A `HumanCheckFailed` event is emitted if the
+        /// account fails the check, but not if the check could not be carried out. Returns the
+        /// `Prosopo` contract that answered the check, if any.
+        fn ensure_faucet_human<B: HumanityBackend>(&mut self, backend: &B, accountid: AccountId) -> Result<Option<AccountId>, Error> {
+            let checked = match self.access_list_verdict(accountid) {
+                Some(verdict) => verdict.map(|()| None),
+                None => self.ensure_human_cached(backend, accountid),
+            };
+            checked.map_err(|reason| {
+                if !reason.is_call_failure() {
+                    self.env().emit_event(HumanCheckFailed {
+                        account: accountid,
+                        reason: reason.clone(),
+                    });
+                }
+                reason
+            })
+        }
+
+        /// Returns the outcome of the faucet humanity check decided by the access lists, `None` if
+        /// `accountid` is on neither list.
+        ///
+        /// The denylist takes precedence over the allowlist.
+        fn access_list_verdict(&self, accountid: AccountId) -> Option<Result<(), Error>> {
+            if self.is_listed(AccessList::Denylist, accountid) {
+                Some(Err(Error::Denylisted))
+            } else if self.is_listed(AccessList::Allowlist, accountid) {
+                Some(Ok(()))
+            } else {
+                None
+            }
+        }
+
+        /// Returns `true` if `accountid` is on `list`.
         #[ink(message)]
-        pub fn balance_of(&self, owner: AccountId) -> Balance {
-            self.balance_of_impl(&owner)
+        pub fn is_listed(&self, list: AccessList, accountid: AccountId) -> bool {
+            self.access_list(list).contains(accountid)
         }
 
-        /// Returns the account balance for the specified `owner`.
+        /// Adds `accounts` to `list`.
         ///
-        /// Returns `0` if the account is non-existent.
+        /// An `AccountListed` event is emitted for every account that was not on `list` yet.
+        /// Cached humanity checks of accounts added to the denylist are dropped.
         ///
-        /// # Note
+        /// # Errors
         ///
-        /// Prefer to call this method over `balance_of` since this
-        /// works using references which are more efficient in Wasm.
-        #[inline]
-        fn balance_of_impl(&self, owner: &AccountId) -> Balance {
-            self.balances.get(owner).unwrap_or_default()
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        #[ink(message)]
+        pub fn add_to_list(&mut self, list: AccessList, accounts: Vec<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            for account in accounts {
+                if list == AccessList::Denylist {
+                    self.verified_at.remove(account);
+                    self.verified_epoch.remove(account);
+                    self.verified_instance.remove(account);
+                }
+                if self.access_list_mut(list).insert(account, &()).is_none() {
+                    self.env().emit_event(AccountListed { list, account });
+                }
+            }
+            Ok(())
+        }
+
+        /// Removes `accounts` from `list`.
+        ///
+        /// An `AccountUnlisted` event is emitted for every account that was on `list`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        #[ink(message)]
+        pub fn remove_from_list(&mut self, list: AccessList, accounts: Vec<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            for account in accounts {
+                if self.is_listed(list, account) {
+                    self.access_list_mut(list).remove(account);
+                    self.env().emit_event(AccountUnlisted { list, account });
+                }
+            }
+            Ok(())
+        }
+
+        /// Returns the storage of `list`.
+        fn access_list(&self, list: AccessList) -> &Mapping<AccountId, ()> {
+            match list {
+                AccessList::Allowlist => &self.allowlisted,
+                AccessList::Denylist => &self.denylisted,
+            }
+        }
+
+        /// Returns the storage of `list` for writing.
+        fn access_list_mut(&mut self, list: AccessList) -> &mut Mapping<AccountId, ()> {
+            match list {
+                AccessList::Allowlist => &mut self.allowlisted,
+                AccessList::Denylist => &mut self.denylisted,
+            }
+        }
+
+        /// Checks that `accountid` passes the humanity check of the faucet without recording the
+        /// result, consulting the access lists before [`Self::check_human`].
+        fn check_faucet_human<B: HumanityBackend>(&self, backend: &B, accountid: AccountId, now: Timestamp) -> Result<HumanCheck, Error> {
+            match self.access_list_verdict(accountid) {
+                Some(verdict) => verdict.map(|()| HumanCheck::Listed),
+                None => self.check_human(backend, accountid, now),
+            }
+        }
+
+        /// Checks that `accountid` passes the humanity check with the configured thresholds,
+        /// without recording the result.
+        ///
+        /// The verification cache is consulted before `backend` is queried.
+        fn check_human<B: HumanityBackend>(&self, backend: &B, accountid: AccountId, now: Timestamp) -> Result<HumanCheck, Error> {
+            if self.is_verification_cached(accountid, now) {
+                return Ok(HumanCheck::Cached(self.verified_instance.get(accountid)));
+            }
+            self.ensure_verified(backend, accountid, self.human_threshold, self.recency_policy)
+                .map(HumanCheck::Verified)
+        }
+
+        /// Checks that `accountid` passes the humanity check with the configured thresholds.
+        ///
+        /// If the verification cache is enabled, a successful check is recorded and reused for
+        /// `verification_cache_ttl` ms without querying `backend` again, unless a parameter of the
+        /// humanity check changes in the meantime. Returns the `Prosopo` contract that answered
+        /// the check, if any, which is cached along with it.
+        fn ensure_human_cached<B: HumanityBackend>(&mut self, backend: &B, accountid: AccountId) -> Result<Option<AccountId>, Error> {
+            let now = self.env().block_timestamp();
+            let prosopo_instance = match self.check_human(backend, accountid, now)? {
+                HumanCheck::Verified(prosopo_instance) => prosopo_instance,
+                check => return Ok(check.prosopo_instance()),
+            };
+            if self.verification_cache_ttl.is_some() {
+                let epoch = self.verification_epoch();
+                self.verified_at.insert(accountid, &now);
+                self.verified_epoch.insert(accountid, &epoch);
+                match prosopo_instance {
+                    Some(instance) => {
+                        self.verified_instance.insert(accountid, &instance);
+                    }
+                    None => self.verified_instance.remove(accountid),
+                }
+            }
+            Ok(prosopo_instance)
+        }
+
+        /// Returns `true` if a successful humanity check of `accountid` is cached, younger than
+        /// the cache TTL at `now` and made with the current parameters of the humanity check.
+        fn is_verification_cached(&self, accountid: AccountId, now: Timestamp) -> bool {
+            match (self.verification_cache_ttl, self.verified_at.get(accountid)) {
+                (Some(ttl), Some(verified_at)) => {
+                    now < verified_at.saturating_add(ttl)
+                        && self.verified_epoch.get(accountid) == Some(self.verification_epoch())
+                }
+                _ => false,
+            }
+        }
+
+        /// Returns the counter of changes to the parameters of the humanity check.
+        fn verification_epoch(&self) -> u32 {
+            self.verification_epoch.get().unwrap_or_default()
+        }
+
+        /// Invalidates every cached humanity check after a parameter of the check changed.
+        fn invalidate_verification_cache(&mut self) {
+            let epoch = self.verification_epoch().wrapping_add(1);
+            self.verification_epoch.set(&epoch);
+        }
+
+        /// Returns the timestamp of the last cached successful humanity check of `accountid`.
+        #[ink(message)]
+        pub fn verified_at(&self, accountid: AccountId) -> Option<Timestamp> {
+            self.verified_at.get(accountid)
+        }
+
+        /// Checks that at least `cooldown` ms have passed since `last_claim`.
+        fn ensure_cooldown(last_claim: Option<Timestamp>, cooldown: Timestamp, now: Timestamp) -> Result<(), Error> {
+            if let Some(last_claim) = last_claim {
+                let next_claim = last_claim.saturating_add(cooldown);
+                if now < next_claim {
+                    return Err(Error::FaucetCooldownActive { remaining_ms: next_claim - now });
+                }
+            }
+            Ok(())
+        }
+
+        /// Checks that paying `amount` to `accountid` stays below its lifetime cap and is covered
+        /// by the faucet reserve.
+        ///
+        /// Returns the total amount `accountid` will have claimed after the payout.
+        fn ensure_faucet_funds(&self, accountid: &AccountId, amount: Balance) -> Result<Balance, Error> {
+            let claimed = self.claimed_impl(accountid).checked_add(amount).ok_or(Error::Overflow)?;
+            if let Some(cap) = self.faucet_lifetime_cap {
+                if claimed > cap {
+                    return Err(Error::FaucetCapReached);
+                }
+            }
+            if self.faucet_reserve() < amount {
+                return Err(Error::FaucetEmpty);
+            }
+            Ok(claimed)
+        }
+
+        /// Calls the registered verifiers to check if `accountid` is human
+        ///
+        /// `Prosopo` verifiers are checked against `threshold` and `recency`. Returns `false`
+        /// if the verifiers reject the user according to the verification policy.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ProsopoCallFailed`, `NoCaptchaHistory` or `VerifierCallFailed` if a
+        /// verifier could not answer the query and `NoVerifiers` if none is registered.
+        #[ink(message)]
+        pub fn is_human(&self, accountid: AccountId, threshold: u8, recency: RecencyPolicy) -> Result<bool, Error> {
+            match self.ensure_verified(&self.humanity_backend(), accountid, threshold, recency) {
+                Ok(_) => Ok(true),
+                Err(Error::UserNotHuman | Error::CaptchaTooOld) => Ok(false),
+                Err(error) => Err(error),
+            }
+        }
+
+        /// Returns the backend querying the deployed verifier contracts.
+        fn humanity_backend(&self) -> ContractBackend {
+            ContractBackend
+        }
+
+        /// Checks `accountid` against the registered verifiers, combining their results
+        /// according to the verification policy.
+        ///
+        /// Returns the `Prosopo` contract that answered the first passing `Prosopo` verifier, if
+        /// one was queried.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NoVerifiers` if no verifier is registered. Otherwise returns the error of the
+        /// first failing verifier under `AllOf` and of the last failing verifier under `AnyOf`
+        /// and `Weighted`.
+        fn ensure_verified<B: HumanityBackend>(&self, backend: &B, accountid: AccountId, threshold: u8, recency: RecencyPolicy) -> Result<Option<AccountId>, Error> {
+            let verifiers = self.verifiers();
+            if verifiers.is_empty() {
+                return Err(Error::NoVerifiers);
+            }
+            let policy = self.verification_policy();
+            let mut passed_weight: u32 = 0;
+            let mut prosopo_instance = None;
+            let mut failure = None;
+            for verifier in verifiers.iter() {
+                match self.ensure_verifier_passes(backend, verifier, accountid, threshold, recency) {
+                    Ok(instance) => {
+                        prosopo_instance = prosopo_instance.or(instance);
+                        passed_weight = passed_weight.saturating_add(verifier.weight);
+                        match policy {
+                            VerificationPolicy::AnyOf => return Ok(prosopo_instance),
+                            VerificationPolicy::Weighted { min_weight } if passed_weight >= min_weight => return Ok(prosopo_instance),
+                            _ => {}
+                        }
+                    }
+                    Err(error) if policy == VerificationPolicy::AllOf => return Err(error),
+                    Err(error) => failure = Some(error),
+                }
+            }
+            match failure {
+                None if policy == VerificationPolicy::AllOf => Ok(prosopo_instance),
+                failure => Err(failure.unwrap_or(Error::UserNotHuman)),
+            }
+        }
+
+        /// Checks `accountid` against a single `verifier`.
+        ///
+        /// Returns the address that answered if `verifier` is a `Prosopo` verifier.
+        fn ensure_verifier_passes<B: HumanityBackend>(&self, backend: &B, verifier: &Verifier, accountid: AccountId, threshold: u8, recency: RecencyPolicy) -> Result<Option<AccountId>, Error> {
+            let selector = match verifier.kind {
+                VerifierKind::Prosopo => {
+                    let ((), instance) = self.with_prosopo_failover(verifier.contract, |instance| {
+                        Self::ensure_human(backend, instance, accountid, threshold, recency)
+                    })?;
+                    return Ok(Some(instance));
+                }
+                VerifierKind::Allowlist => IS_ALLOWED_SELECTOR,
+                VerifierKind::Attestation => HAS_ATTESTATION_SELECTOR,
+            };
+            if !backend.query_verifier(verifier.contract, selector, accountid)? {
+                return Err(Error::UserNotHuman);
+            }
+            Ok(None)
+        }
+
+        /// Runs `query` against the `Prosopo` verifier at `primary`, moving on to the next
+        /// fallback address whenever the call itself fails. Errors the contract answers with are
+        /// returned without trying the fallbacks.
+        ///
+        /// Returns the result together with the address that answered.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ProsopoCallFailed` if no address answered, otherwise the error `query`
+        /// returned for the first address that did.
+        fn with_prosopo_failover<T, F>(&self, primary: AccountId, mut query: F) -> Result<(T, AccountId), Error>
+        where
+            F: FnMut(AccountId) -> Result<T, Error>,
+        {
+            for instance in self.prosopo_instances(primary) {
+                match query(instance) {
+                    Err(Error::ProsopoCallFailed) => continue,
+                    result => return result.map(|value| (value, instance)),
+                }
+            }
+            Err(Error::ProsopoCallFailed)
+        }
+
+        /// Returns `primary` followed by its fallback addresses in the order they are tried.
+        fn prosopo_instances(&self, primary: AccountId) -> Vec<AccountId> {
+            let mut instances = vec![primary];
+            instances.extend(self.prosopo_fallbacks.get(primary).unwrap_or_default());
+            instances
+        }
+
+        /// Returns the address of the first registered `Prosopo` verifier, if any.
+        fn primary_prosopo(&self) -> Option<AccountId> {
+            self.verifiers()
+                .into_iter()
+                .find(|verifier| verifier.kind == VerifierKind::Prosopo)
+                .map(|verifier| verifier.contract)
+        }
+
+        /// Asks the `Prosopo` contract at `prosopo_account` through `backend` whether `accountid`
+        /// is human and has answered a captcha correctly within the window of `recency`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ProsopoCallFailed` if the humanity query fails, `NoCaptchaHistory` if
+        /// `Prosopo` has no correct captcha recorded for `accountid`, `CaptchaTooOld` if the
+        /// last correct captcha is too old and `UserNotHuman` if the threshold is not met.
+        fn ensure_human<B: HumanityBackend>(backend: &B, prosopo_account: AccountId, accountid: AccountId, threshold: u8, recency: RecencyPolicy) -> Result<(), Error> {
+            // check that the captcha was completed within the recency window first so that a
+            // stale captcha skips the more expensive humanity query
+            if u64::from(backend.last_correct_captcha_ms(prosopo_account, accountid)?) > recency.window_ms() {
+                return Err(Error::CaptchaTooOld);
+            }
+            if !backend.is_human_user(prosopo_account, accountid, threshold)? {
+                return Err(Error::UserNotHuman);
+            }
+            Ok(())
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        ///
+        /// On success a `Transfer` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `UserNotHuman` error if `value` is above the large transfer threshold and
+        /// the caller does not pass the humanity check, or the error of the verifiers if they
+        /// could not answer it.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), Error> {
+            self.ensure_not_paused(PauseTarget::Transfers)?;
+            let from = self.env().caller();
+            let backend = self.humanity_backend();
+            self.ensure_transfer_allowed(&backend, from, value)?;
+            self.transfer_from_to(&from, &to, value)
+        }
+
+        /// Checks that `from` passes the humanity check if `value` is above the large transfer
+        /// threshold.
+        ///
+        /// A failed check is reported as `UserNotHuman`, while `ProsopoCallFailed`,
+        /// `VerifierCallFailed` and `NoVerifiers` are passed through.
+        fn ensure_transfer_allowed<B: HumanityBackend>(&mut self, backend: &B, from: AccountId, value: Balance) -> Result<(), Error> {
+            match self.large_transfer_threshold {
+                Some(threshold) if value > threshold => self.ensure_human_holder(backend, from),
+                _ => Ok(()),
+            }
+        }
+
+        /// Checks that the token holder `accountid` passes the humanity check.
+        ///
+        /// A failed check is reported as `UserNotHuman`, while `ProsopoCallFailed`,
+        /// `VerifierCallFailed` and `NoVerifiers` are passed through.
+        fn ensure_human_holder<B: HumanityBackend>(&mut self, backend: &B, accountid: AccountId) -> Result<(), Error> {
+            self.ensure_human_cached(backend, accountid).map(|_| ()).map_err(|error| {
+                if error.is_call_failure() {
+                    error
+                } else {
+                    Error::UserNotHuman
+                }
+            })
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        ///
+        /// Returns `0` if no allowance has been set.
+        #[ink(message, selector = 0x4d47d921)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowance_impl(&owner, &spender)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        ///
+        /// If this function is called again it overwrites the current allowance with `value`.
+        ///
+        /// An `Approval` event is emitted.
+        #[ink(message, selector = 0xb20f1bbd)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), Error> {
+            self.ensure_not_paused(PauseTarget::Allowances)?;
+            let owner = self.env().caller();
+            self.approve_impl(&owner, &spender, value);
+            Ok(())
+        }
+
+        /// Atomically increases the allowance granted to `spender` by the caller.
+        ///
+        /// An `Approval` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Overflow` error if the new allowance would overflow.
+        #[ink(message, selector = 0x96d6b57a)]
+        pub fn increase_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), Error> {
+            self.ensure_not_paused(PauseTarget::Allowances)?;
+            let owner = self.env().caller();
+            let allowance = self.allowance_impl(&owner, &spender);
+            let allowance = allowance.checked_add(delta_value).ok_or(Error::Overflow)?;
+            self.approve_impl(&owner, &spender, allowance);
+            Ok(())
+        }
+
+        /// Atomically decreases the allowance granted to `spender` by the caller.
+        ///
+        /// An `Approval` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientAllowance` error if `delta_value` exceeds the current
+        /// allowance.
+        #[ink(message, selector = 0xfecb57d5)]
+        pub fn decrease_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), Error> {
+            self.ensure_not_paused(PauseTarget::Allowances)?;
+            let owner = self.env().caller();
+            let allowance = self.allowance_impl(&owner, &spender)
+                .checked_sub(delta_value)
+                .ok_or(Error::InsufficientAllowance)?;
+            self.approve_impl(&owner, &spender, allowance);
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on the behalf of `from` to the account `to`.
+        ///
+        /// This can be used to allow a contract to transfer tokens on ones behalf and/or
+        /// to charge fees in sub-currencies, for example.
+        ///
+        /// On success a `Transfer` and an `Approval` event are emitted. `data` is accepted for
+        /// PSP22 compatibility and ignored.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientAllowance` error if there are not enough tokens allowed
+        /// for the caller to withdraw from `from`.
+        ///
+        /// Returns `UserNotHuman` error if `value` is above the large transfer threshold and
+        /// `from` does not pass the humanity check, or the error of the verifiers if they could
+        /// not answer it.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the account balance of `from`.
+        #[ink(message, selector = 0x54b3c76e)]
+        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, value: Balance, _data: Vec<u8>) -> Result<(), Error> {
+            self.ensure_not_paused(PauseTarget::Transfers)?;
+            self.ensure_not_paused(PauseTarget::Allowances)?;
+            let caller = self.env().caller();
+            let allowance = self.allowance_impl(&from, &caller)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientAllowance)?;
+            let backend = self.humanity_backend();
+            self.ensure_transfer_allowed(&backend, from, value)?;
+            self.transfer_from_to(&from, &to, value)?;
+            self.approve_impl(&from, &caller, allowance);
+            Ok(())
+        }
+
+        /// Creates `value` new tokens and assigns them to `to`, increasing the total supply.
+        ///
+        /// On success a `Transfer` event with no sender is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `Overflow` error if the balance of `to` or the total supply would overflow.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let to_balance = self.balance_of_impl(&to).checked_add(value).ok_or(Error::Overflow)?;
+            let total_supply = self.total_supply.checked_add(value).ok_or(Error::Overflow)?;
+            self.balances.insert(to, &to_balance);
+            self.total_supply = total_supply;
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Destroys `value` tokens from the caller's account, reducing the total supply.
+        ///
+        /// The caller must pass the humanity check. On success a `Transfer` event with no
+        /// recipient is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `UserNotHuman` error if the caller does not pass the humanity check, or the
+        /// error of the verifiers if they could not answer it.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        #[ink(message)]
+        pub fn burn(&mut self, value: Balance) -> Result<(), Error> {
+            let backend = self.humanity_backend();
+            self.burn_with(&backend, value)
+        }
+
+        /// Burns `value` tokens from the caller's account using `backend` for the humanity check.
+        fn burn_with<B: HumanityBackend>(&mut self, backend: &B, value: Balance) -> Result<(), Error> {
+            self.ensure_not_paused(PauseTarget::Transfers)?;
+            let from = self.env().caller();
+            self.ensure_human_holder(backend, from)?;
+            self.burn_impl(&from, value)
+        }
+
+        /// Destroys `value` tokens from the account `from` using the allowance granted to the
+        /// caller, reducing the total supply.
+        ///
+        /// On success a `Transfer` event with no recipient and an `Approval` event are emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `InsufficientAllowance` error if there are not enough tokens allowed
+        /// for the caller to withdraw from `from`.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the account balance of `from`.
+        #[ink(message)]
+        pub fn burn_from(&mut self, from: AccountId, value: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.ensure_not_paused(PauseTarget::Transfers)?;
+            self.ensure_not_paused(PauseTarget::Allowances)?;
+            let caller = self.env().caller();
+            let allowance = self.allowance_impl(&from, &caller)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientAllowance)?;
+            self.burn_impl(&from, value)?;
+            self.approve_impl(&from, &caller, allowance);
+            Ok(())
+        }
+
+        /// Destroys `value` tokens from the account `from`, reducing the total supply.
+        ///
+        /// On success a `Transfer` event with no recipient is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the account balance of `from`.
+        fn burn_impl(&mut self, from: &AccountId, value: Balance) -> Result<(), Error> {
+            let from_balance = self.balance_of_impl(from)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientBalance)?;
+            let total_supply = self.total_supply.checked_sub(value).ok_or(Error::Overflow)?;
+            self.balances.insert(from, &from_balance);
+            self.total_supply = total_supply;
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: None,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Sets the allowance of `spender` over the tokens of `owner` to `value`.
+        ///
+        /// An `Approval` event is emitted.
+        fn approve_impl(&mut self, owner: &AccountId, spender: &AccountId, value: Balance) {
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner: *owner,
+                spender: *spender,
+                value,
+            });
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        ///
+        /// On success a `Transfer` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        ///
+        /// Returns `Overflow` error if the balance of `to` would overflow.
+        fn transfer_from_to(
+            &mut self,
+            from: &AccountId,
+            to: &AccountId,
+            value: Balance,
+        ) -> Result<(), Error> {
+            let from_balance = self.balance_of_impl(from)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientBalance)?;
+            self.balances.insert(from, &from_balance);
+            let to_balance = self.balance_of_impl(to).checked_add(value).ok_or(Error::Overflow)?;
+            self.balances.insert(to, &to_balance);
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Returns every contract parameter.
+        #[ink(message)]
+        pub fn config(&self) -> DappConfig {
+            DappConfig {
+                owner: self.owner,
+                guardian: self.guardian,
+                token_holder: self.token_holder,
+                verifiers: self.verifiers(),
+                verification_policy: self.verification_policy(),
+                prosopo_accounts: self.prosopo_accounts(),
+                faucet_amount: self.faucet_amount,
+                human_threshold: self.human_threshold,
+                recency_policy: self.recency_policy,
+                faucet_cooldown: self.faucet_cooldown,
+                faucet_lifetime_cap: self.faucet_lifetime_cap,
+                faucet_caller_only: self.faucet_caller_only,
+                faucet_tiers: self.faucet_tiers(),
+                large_transfer_threshold: self.large_transfer_threshold,
+                native_faucet_amount: self.native_faucet_amount,
+                native_faucet_cooldown: self.native_faucet_cooldown,
+                verification_cache_ttl: self.verification_cache_ttl,
+            }
+        }
+
+        /// Returns the owner of the contract.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Transfers the ownership of the contract to `new_owner`.
+        ///
+        /// An `OwnershipTransferred` event is emitted.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let previous_owner = self.owner;
+            self.owner = new_owner;
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner,
+            });
+            Ok(())
+        }
+
+        /// Sets the amount of tokens paid out per faucet claim.
+        #[ink(message)]
+        pub fn set_faucet_amount(&mut self, faucet_amount: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.faucet_amount = faucet_amount;
+            self.emit_config_changed(ConfigParameter::FaucetAmount(faucet_amount));
+            Ok(())
+        }
+
+        /// Sets the percentage of correct captchas an account must have answered.
+        #[ink(message)]
+        pub fn set_human_threshold(&mut self, human_threshold: u8) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.human_threshold = human_threshold;
+            self.invalidate_verification_cache();
+            self.emit_config_changed(ConfigParameter::HumanThreshold(human_threshold));
+            Ok(())
+        }
+
+        /// Returns how recently an account must have answered a captcha.
+        #[ink(message)]
+        pub fn recency_policy(&self) -> RecencyPolicy {
+            self.recency_policy
+        }
+
+        /// Sets how recently an account must have answered a captcha.
+        #[ink(message)]
+        pub fn set_recency_policy(&mut self, recency_policy: RecencyPolicy) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.recency_policy = recency_policy;
+            self.invalidate_verification_cache();
+            self.emit_config_changed(ConfigParameter::RecencyPolicy(recency_policy));
+            Ok(())
+        }
+
+        /// Returns the contracts consulted to decide whether an account is human.
+        #[ink(message)]
+        pub fn verifiers(&self) -> Vec<Verifier> {
+            self.verifiers.get().unwrap_or_default()
+        }
+
+        /// Registers `verifier` to take part in the humanity check.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `TooManyVerifiers` error if `MAX_VERIFIERS` verifiers are already registered.
+        ///
+        /// Returns `InvalidVerifier` error if `verifier` has a weight of zero or its contract is
+        /// already registered.
+        #[ink(message)]
+        pub fn add_verifier(&mut self, verifier: Verifier) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let mut verifiers = self.verifiers();
+            if verifiers.len() >= MAX_VERIFIERS {
+                return Err(Error::TooManyVerifiers);
+            }
+            verifiers.push(verifier);
+            self.set_verifiers(verifiers)
+        }
+
+        /// Removes every verifier registered for `contract`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `InvalidVerificationPolicy` error if the remaining verifiers could no longer
+        /// reach the `min_weight` of a weighted policy.
+        #[ink(message)]
+        pub fn remove_verifier(&mut self, contract: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let mut verifiers = self.verifiers();
+            verifiers.retain(|verifier| verifier.contract != contract);
+            self.set_verifiers(verifiers)?;
+            self.prosopo_fallbacks.remove(contract);
+            Ok(())
+        }
+
+        /// Returns the addresses of the first `Prosopo` verifier in the order they are tried.
+        ///
+        /// Returns an empty list if no `Prosopo` verifier is registered.
+        #[ink(message)]
+        pub fn prosopo_accounts(&self) -> Vec<AccountId> {
+            self.primary_prosopo()
+                .map(|primary| self.prosopo_instances(primary))
+                .unwrap_or_default()
+        }
+
+        /// Sets the ordered addresses of the first `Prosopo` verifier.
+        ///
+        /// The first address becomes the primary and the rest are tried in order whenever a call
+        /// to the previous one fails. A `Prosopo` verifier with weight `1` is registered if there
+        /// is none yet.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `InvalidProsopoAccounts` error if `prosopo_accounts` is empty or longer than
+        /// `MAX_PROSOPO_ACCOUNTS`.
+        ///
+        /// Returns `TooManyVerifiers` error if a verifier has to be registered and
+        /// `MAX_VERIFIERS` verifiers are already registered.
+        ///
+        /// Returns `InvalidVerifier` error if the primary address is registered as another
+        /// verifier.
+        #[ink(message)]
+        pub fn set_prosopo_accounts(&mut self, prosopo_accounts: Vec<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let (primary, fallbacks) = prosopo_accounts
+                .split_first()
+                .filter(|_| prosopo_accounts.len() <= MAX_PROSOPO_ACCOUNTS)
+                .ok_or(Error::InvalidProsopoAccounts)?;
+            let mut verifiers = self.verifiers();
+            match verifiers.iter_mut().find(|verifier| verifier.kind == VerifierKind::Prosopo) {
+                Some(verifier) => {
+                    self.prosopo_fallbacks.remove(verifier.contract);
+                    verifier.contract = *primary;
+                }
+                None if verifiers.len() >= MAX_VERIFIERS => return Err(Error::TooManyVerifiers),
+                None => verifiers.push(Verifier {
+                    contract: *primary,
+                    kind: VerifierKind::Prosopo,
+                    weight: 1,
+                }),
+            }
+            self.set_verifiers(verifiers)?;
+            self.prosopo_fallbacks.insert(primary, &fallbacks.to_vec());
+            self.emit_config_changed(ConfigParameter::ProsopoAccounts(prosopo_accounts));
+            Ok(())
+        }
+
+        /// Stores `verifiers` as the verifier registry after checking it against the
+        /// verification policy.
+        fn set_verifiers(&mut self, verifiers: Vec<Verifier>) -> Result<(), Error> {
+            Self::ensure_valid_verifiers(&verifiers, self.verification_policy())?;
+            self.verifiers.set(&verifiers);
+            self.invalidate_verification_cache();
+            self.emit_config_changed(ConfigParameter::Verifiers(verifiers));
+            Ok(())
+        }
+
+        /// Checks that every verifier has a non-zero weight and a distinct contract, and that
+        /// `policy` can be satisfied by them.
+        fn ensure_valid_verifiers(verifiers: &[Verifier], policy: VerificationPolicy) -> Result<(), Error> {
+            for (index, verifier) in verifiers.iter().enumerate() {
+                if verifier.weight == 0
+                    || verifiers[..index].iter().any(|other| other.contract == verifier.contract)
+                {
+                    return Err(Error::InvalidVerifier);
+                }
+            }
+            if let VerificationPolicy::Weighted { min_weight } = policy {
+                let total_weight = verifiers
+                    .iter()
+                    .fold(0u32, |total, verifier| total.saturating_add(verifier.weight));
+                if min_weight > total_weight {
+                    return Err(Error::InvalidVerificationPolicy);
+                }
+            }
+            Ok(())
+        }
+
+        /// Returns how the results of the verifiers are combined.
+        #[ink(message)]
+        pub fn verification_policy(&self) -> VerificationPolicy {
+            self.verification_policy.get().unwrap_or(VerificationPolicy::AnyOf)
+        }
+
+        /// Sets how the results of the verifiers are combined.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `InvalidVerificationPolicy` error if `verification_policy` is weighted with a
+        /// `min_weight` above the total weight of the registered verifiers.
+        #[ink(message)]
+        pub fn set_verification_policy(&mut self, verification_policy: VerificationPolicy) -> Result<(), Error> {
+            self.ensure_owner()?;
+            Self::ensure_valid_verifiers(&self.verifiers(), verification_policy)?;
+            self.verification_policy.set(&verification_policy);
+            self.invalidate_verification_cache();
+            self.emit_config_changed(ConfigParameter::VerificationPolicy(verification_policy));
+            Ok(())
+        }
+
+        /// Sets the time in ms an account must wait between two faucet claims.
+        #[ink(message)]
+        pub fn set_faucet_cooldown(&mut self, faucet_cooldown: Timestamp) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.faucet_cooldown = faucet_cooldown;
+            self.emit_config_changed(ConfigParameter::FaucetCooldown(faucet_cooldown));
+            Ok(())
+        }
+
+        /// Sets the maximum amount of tokens an account can claim from the faucet in total.
+        #[ink(message)]
+        pub fn set_faucet_lifetime_cap(&mut self, faucet_lifetime_cap: Option<Balance>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.faucet_lifetime_cap = faucet_lifetime_cap;
+            self.emit_config_changed(ConfigParameter::FaucetLifetimeCap(faucet_lifetime_cap));
+            Ok(())
+        }
+
+        /// Sets whether the faucet only pays out to the caller or the accounts it relays for.
+        #[ink(message)]
+        pub fn set_faucet_caller_only(&mut self, faucet_caller_only: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.faucet_caller_only = faucet_caller_only;
+            self.emit_config_changed(ConfigParameter::FaucetCallerOnly(faucet_caller_only));
+            Ok(())
+        }
+
+        /// Allows or disallows `relayer` to submit faucet claims on behalf of other accounts.
+        #[ink(message)]
+        pub fn set_relayer(&mut self, relayer: AccountId, allowed: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.relayers.insert(&relayer, &allowed);
+            self.emit_config_changed(ConfigParameter::Relayer(relayer, allowed));
+            Ok(())
+        }
+
+        /// Sets the amount above which the sender of a transfer must pass the humanity check.
+        ///
+        /// `None` disables the check for all transfers.
+        #[ink(message)]
+        pub fn set_large_transfer_threshold(&mut self, large_transfer_threshold: Option<Balance>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.large_transfer_threshold = large_transfer_threshold;
+            self.emit_config_changed(ConfigParameter::LargeTransferThreshold(large_transfer_threshold));
+            Ok(())
+        }
+
+        /// Sets the amount of native currency paid out per native faucet claim.
+        ///
+        /// `None` disables the native faucet.
+        #[ink(message)]
+        pub fn set_native_faucet_amount(&mut self, native_faucet_amount: Option<Balance>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.native_faucet_amount = native_faucet_amount;
+            self.emit_config_changed(ConfigParameter::NativeFaucetAmount(native_faucet_amount));
+            Ok(())
+        }
+
+        /// Sets the time in ms an account must wait between two native faucet claims.
+        #[ink(message)]
+        pub fn set_native_faucet_cooldown(&mut self, native_faucet_cooldown: Timestamp) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.native_faucet_cooldown = native_faucet_cooldown;
+            self.emit_config_changed(ConfigParameter::NativeFaucetCooldown(native_faucet_cooldown));
+            Ok(())
+        }
+
+        /// Sets the time in ms for which a successful humanity check of an account is reused.
+        ///
+        /// `None` disables the verification cache.
+        #[ink(message)]
+        pub fn set_verification_cache_ttl(&mut self, verification_cache_ttl: Option<Timestamp>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.verification_cache_ttl = verification_cache_ttl;
+            self.emit_config_changed(ConfigParameter::VerificationCacheTtl(verification_cache_ttl));
+            Ok(())
+        }
+
+        /// Removes the cached humanity checks of `accounts` so their next gated action queries
+        /// the humanity backend again.
+        #[ink(message)]
+        pub fn invalidate_verifications(&mut self, accounts: Vec<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            for account in accounts.iter() {
+                self.verified_at.remove(account);
+                self.verified_epoch.remove(account);
+                self.verified_instance.remove(account);
+            }
+            Ok(())
+        }
+
+        /// Sets the faucet payout tiers.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InvalidFaucetTiers` error if the thresholds are not strictly ascending, exceed
+        /// 100 percent or there are more than `MAX_FAUCET_TIERS` tiers.
+        #[ink(message)]
+        pub fn set_faucet_tiers(&mut self, faucet_tiers: Vec<FaucetTier>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if faucet_tiers.len() > MAX_FAUCET_TIERS
+                || faucet_tiers.iter().any(|tier| tier.threshold > 100)
+                || faucet_tiers.windows(2).any(|pair| pair[0].threshold >= pair[1].threshold)
+            {
+                return Err(Error::InvalidFaucetTiers);
+            }
+            self.faucet_tiers.set(&faucet_tiers);
+            self.emit_config_changed(ConfigParameter::FaucetTiers(faucet_tiers));
+            Ok(())
+        }
+
+        /// Returns the guardian of the contract, if any.
+        #[ink(message)]
+        pub fn guardian(&self) -> Option<AccountId> {
+            self.guardian
+        }
+
+        /// Sets the account allowed to pause the contract alongside the owner.
+        #[ink(message)]
+        pub fn set_guardian(&mut self, guardian: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.guardian = guardian;
+            self.emit_config_changed(ConfigParameter::Guardian(guardian));
+            Ok(())
+        }
+
+        /// Returns `true` if `target` is paused.
+        #[ink(message)]
+        pub fn is_paused(&self, target: PauseTarget) -> bool {
+            match target {
+                PauseTarget::Faucet => self.faucet_paused,
+                PauseTarget::Transfers => self.transfers_paused,
+                PauseTarget::Allowances => self.allowances_paused,
+            }
+        }
+
+        /// Pauses `target` until the owner unpauses it.
+        ///
+        /// A `Paused` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotGuardian` error if the caller is neither the owner nor the guardian.
+        #[ink(message)]
+        pub fn pause(&mut self, target: PauseTarget) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller != self.owner && Some(caller) != self.guardian {
+                return Err(Error::NotGuardian);
+            }
+            self.set_paused(target, true);
+            self.env().emit_event(Paused { target, by: caller });
+            Ok(())
+        }
+
+        /// Unpauses `target`.
+        ///
+        /// An `Unpaused` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        #[ink(message)]
+        pub fn unpause(&mut self, target: PauseTarget) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.set_paused(target, false);
+            self.env().emit_event(Unpaused {
+                target,
+                by: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Sets the pause flag of `target` to `paused`.
+        fn set_paused(&mut self, target: PauseTarget, paused: bool) {
+            match target {
+                PauseTarget::Faucet => self.faucet_paused = paused,
+                PauseTarget::Transfers => self.transfers_paused = paused,
+                PauseTarget::Allowances => self.allowances_paused = paused,
+            }
+        }
+
+        /// Returns `Paused` if `target` is paused.
+        fn ensure_not_paused(&self, target: PauseTarget) -> Result<(), Error> {
+            if self.is_paused(target) {
+                return Err(Error::Paused);
+            }
+            Ok(())
+        }
+
+        /// Replaces the code of the contract with the code at `code_hash`, keeping its storage.
+        ///
+        /// Call `migrate` afterwards if the new code uses a newer storage layout version.
+        ///
+        /// An `Upgraded` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `UpgradeFailed` error if no code is stored under `code_hash`.
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.env().set_code_hash(&code_hash).map_err(|_| Error::UpgradeFailed)?;
+            self.env().emit_event(Upgraded { code_hash });
+            Ok(())
+        }
+
+        /// Returns the version of the storage layout the stored data was written with.
+        ///
+        /// Returns `0` for storage written before the layout was versioned.
+        #[ink(message)]
+        pub fn storage_version(&self) -> u32 {
+            self.storage_version.get().unwrap_or_default()
+        }
+
+        /// Migrates the stored data to the storage layout version of the current code.
+        ///
+        /// A `Migrated` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner of the contract.
+        ///
+        /// Returns `NothingToMigrate` error if the storage is already at `STORAGE_VERSION`.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let from_version = self.storage_version();
+            if from_version >= STORAGE_VERSION {
+                return Err(Error::NothingToMigrate);
+            }
+            // Layout changes are applied one version at a time. Version 1 only introduced the
+            // version cell itself.
+            if from_version < 2 {
+                self.verifiers.set(&vec![Verifier {
+                    contract: self.prosopo_account,
+                    kind: VerifierKind::Prosopo,
+                    weight: 1,
+                }]);
+                self.verification_policy.set(&VerificationPolicy::AnyOf);
+            }
+            self.storage_version.set(&STORAGE_VERSION);
+            self.env().emit_event(Migrated {
+                from_version,
+                to_version: STORAGE_VERSION,
+            });
+            Ok(())
+        }
+
+        /// Returns `NotOwner` if the caller is not the owner of the contract.
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+
+        /// Emits a `ConfigChanged` event for `parameter`.
+        fn emit_config_changed(&self, parameter: ConfigParameter) {
+            self.env().emit_event(ConfigChanged { parameter });
+        }
+
+        /// Returns the total token supply.
+        #[ink(message, selector = 0x162df8c2)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the token name.
+        #[ink(message, selector = 0x3d261bd4)]
+        pub fn token_name(&self) -> Option<String> {
+            self.token_name.get()
+        }
+
+        /// Returns the token symbol.
+        #[ink(message, selector = 0x34205be5)]
+        pub fn token_symbol(&self) -> Option<String> {
+            self.token_symbol.get()
+        }
+
+        /// Returns the token decimals.
+        #[ink(message, selector = 0x7271b782)]
+        pub fn token_decimals(&self) -> u8 {
+            self.token_decimals
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        ///
+        /// Returns `0` if the account is non-existent.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balance_of_impl(&owner)
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        ///
+        /// Returns `0` if the account is non-existent.
+        ///
+        /// # Note
+        ///
+        /// Prefer to call this method over `balance_of` since this
+        /// works using references which are more efficient in Wasm.
+        #[inline]
+        fn balance_of_impl(&self, owner: &AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        /// Returns the total amount `accountid` has claimed from the faucet.
+        #[inline]
+        fn claimed_impl(&self, accountid: &AccountId) -> Balance {
+            self.claimed.get(accountid).unwrap_or_default()
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        ///
+        /// Returns `0` if no allowance has been set.
+        ///
+        /// # Note
+        ///
+        /// Prefer to call this method over `allowance` since this
+        /// works using references which are more efficient in Wasm.
+        #[inline]
+        fn allowance_impl(&self, owner: &AccountId, spender: &AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use core::cell::Cell;
+        use ink::env::test;
+
+        const INITIAL_SUPPLY: Balance = 1_000_000;
+        const FAUCET_AMOUNT: Balance = 100;
+        const HUMAN_THRESHOLD: u8 = 80;
+        const RECENCY_MS: u64 = 180_000;
+        const NATIVE_FAUCET_AMOUNT: Balance = 50;
+
+        type Event = <Dapp as ink::reflect::ContractEventBase>::Type;
+
+        /// Humanity backend answering every query with fixed values and counting the queries.
+        struct MockBackend {
+            last_correct_captcha_ms: Result<u32, Error>,
+            human_score: Result<u8, Error>,
+            /// `Prosopo` address whose calls fail as if the contract could not be reached
+            unreachable: Option<AccountId>,
+            /// Answers of the allowlist and attestation verifiers, which fail to be called otherwise
+            verifier_answers: Vec<(AccountId, Result<bool, Error>)>,
+            captcha_calls: Cell<u32>,
+            human_calls: Cell<u32>,
+        }
+
+        impl MockBackend {
+            fn new(last_correct_captcha_ms: Result<u32, Error>, human_score: Result<u8, Error>) -> Self {
+                Self {
+                    last_correct_captcha_ms,
+                    human_score,
+                    unreachable: None,
+                    verifier_answers: Vec::new(),
+                    captcha_calls: Cell::new(0),
+                    human_calls: Cell::new(0),
+                }
+            }
+
+            fn human() -> Self {
+                Self::new(Ok(1_000), Ok(100))
+            }
+
+            fn not_human() -> Self {
+                Self::new(Ok(1_000), Ok(HUMAN_THRESHOLD - 1))
+            }
+
+            fn stale() -> Self {
+                Self::new(Ok(RECENCY_MS as u32 + 1), Ok(100))
+            }
+
+            fn failing() -> Self {
+                Self::new(Err(Error::ProsopoCallFailed), Err(Error::ProsopoCallFailed))
+            }
+
+            fn no_history() -> Self {
+                Self::new(Err(Error::NoCaptchaHistory), Err(Error::NoCaptchaHistory))
+            }
+
+            fn with_verifier(mut self, contract: AccountId, answer: Result<bool, Error>) -> Self {
+                self.verifier_answers.push((contract, answer));
+                self
+            }
+
+            fn ensure_reachable(&self, prosopo_account: AccountId) -> Result<(), Error> {
+                if self.unreachable == Some(prosopo_account) {
+                    return Err(Error::ProsopoCallFailed);
+                }
+                Ok(())
+            }
+        }
+
+        impl HumanityBackend for MockBackend {
+            fn last_correct_captcha_ms(&self, prosopo_account: AccountId, _accountid: AccountId) -> Result<u32, Error> {
+                self.captcha_calls.set(self.captcha_calls.get() + 1);
+                self.ensure_reachable(prosopo_account)?;
+                self.last_correct_captcha_ms.clone()
+            }
+
+            fn is_human_user(&self, prosopo_account: AccountId, _accountid: AccountId, threshold: u8) -> Result<bool, Error> {
+                self.human_calls.set(self.human_calls.get() + 1);
+                self.ensure_reachable(prosopo_account)?;
+                self.human_score.clone().map(|score| score >= threshold)
+            }
+
+            fn query_verifier(&self, contract: AccountId, _selector: [u8; 4], _accountid: AccountId) -> Result<bool, Error> {
+                self.verifier_answers
+                    .iter()
+                    .find(|(verifier, _)| *verifier == contract)
+                    .map_or(Err(Error::VerifierCallFailed), |(_, answer)| answer.clone())
+            }
+        }
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn contract_account() -> AccountId {
+            AccountId::from([0xcc; 32])
+        }
+
+        fn prosopo_account() -> AccountId {
+            AccountId::from([0xaa; 32])
+        }
+
+        fn allowlist_account() -> AccountId {
+            AccountId::from([0xbb; 32])
+        }
+
+        fn attestation_account() -> AccountId {
+            AccountId::from([0xdd; 32])
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        /// Deploys the contract from alice, who receives the initial supply.
+        fn deploy() -> Dapp {
+            test::set_callee::<DefaultEnvironment>(contract_account());
+            set_caller(accounts().alice);
+            Dapp::new(
+                INITIAL_SUPPLY,
+                FAUCET_AMOUNT,
+                prosopo_account(),
+                HUMAN_THRESHOLD,
+                RecencyPolicy::Milliseconds(RECENCY_MS),
+                None,
+                None,
+                12,
+                0,
+                None,
+                false,
+                Vec::new(),
+            )
+        }
+
+        /// Deploys the contract and moves `reserve` tokens of alice into the faucet reserve.
+        fn deploy_with_reserve(reserve: Balance) -> Dapp {
+            let mut dapp = deploy();
+            dapp.fund_faucet(reserve).expect("alice holds the initial supply");
+            dapp
+        }
+
+        /// Deploys the contract with the native faucet enabled and `reserve` native currency
+        /// held by the contract and set aside for it.
+        fn deploy_with_native_reserve(reserve: Balance) -> Dapp {
+            let mut dapp = deploy();
+            test::set_account_balance::<DefaultEnvironment>(contract_account(), reserve);
+            test::set_value_transferred::<DefaultEnvironment>(reserve);
+            dapp.fund_native_faucet().expect("the reserve fits in a balance");
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            dapp.set_native_faucet_amount(Some(NATIVE_FAUCET_AMOUNT)).expect("alice is the owner");
+            dapp
+        }
+
+        fn native_balance(account: AccountId) -> Balance {
+            test::get_account_balance::<DefaultEnvironment>(account).expect("account has a balance")
+        }
+
+        fn recency() -> RecencyPolicy {
+            RecencyPolicy::Milliseconds(RECENCY_MS)
+        }
+
+        /// Returns the events emitted so far, oldest first.
+        fn recorded_events() -> Vec<Event> {
+            test::recorded_events()
+                .map(|event| {
+                    <Event as scale::Decode>::decode(&mut &event.data[..])
+                        .expect("encountered invalid contract event data buffer")
+                })
+                .collect()
+        }
+
+        /// Deterministic xorshift generator driving the randomised supply tests.
+        struct Rng(u64);
+
+        impl Rng {
+            fn next_u64(&mut self) -> u64 {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 7;
+                self.0 ^= self.0 << 17;
+                self.0
+            }
+
+            fn below(&mut self, bound: u64) -> u64 {
+                self.next_u64() % bound
+            }
+        }
+
+        fn assert_transfer_event(event: &Event, from: Option<AccountId>, to: Option<AccountId>, value: Balance) {
+            match event {
+                Event::Transfer(transfer) => {
+                    assert_eq!(transfer.from, from);
+                    assert_eq!(transfer.to, to);
+                    assert_eq!(transfer.value, value);
+                }
+                _ => panic!("expected a Transfer event"),
+            }
+        }
+
+        #[ink::test]
+        fn faucet_pays_out_to_humans() {
+            let mut dapp = deploy_with_reserve(1_000);
+            let bob = accounts().bob;
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), FAUCET_AMOUNT);
+            assert_eq!(dapp.faucet_reserve(), 1_000 - FAUCET_AMOUNT);
+            assert_eq!(dapp.last_claim(bob), Some(0));
+            assert_eq!(dapp.claimed(bob), FAUCET_AMOUNT);
+        }
+
+        #[ink::test]
+        fn faucet_rejects_failed_humanity_checks() {
+            let bob = accounts().bob;
+            let cases = [
+                (MockBackend::not_human(), Error::UserNotHuman),
+                (MockBackend::stale(), Error::CaptchaTooOld),
+                (MockBackend::no_history(), Error::NoCaptchaHistory),
+            ];
+            for (backend, reason) in cases {
+                let mut dapp = deploy_with_reserve(1_000);
+                assert_eq!(dapp.faucet_with(&backend, bob), Ok(FaucetOutcome::HumanCheckFailed(reason)));
+                assert_eq!(dapp.balance_of(bob), 0);
+                assert_eq!(dapp.faucet_reserve(), 1_000);
+                assert_eq!(dapp.last_claim(bob), None);
+            }
+        }
+
+        #[ink::test]
+        fn faucet_fails_when_the_humanity_check_cannot_be_made() {
+            let mut dapp = deploy_with_reserve(1_000);
+            let bob = accounts().bob;
+            let events = recorded_events().len();
+            assert_eq!(dapp.faucet_with(&MockBackend::failing(), bob), Err(Error::ProsopoCallFailed));
+            assert_eq!(recorded_events().len(), events);
+            assert_eq!(dapp.remove_verifier(prosopo_account()), Ok(()));
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Err(Error::NoVerifiers));
+            assert_eq!(dapp.balance_of(bob), 0);
+            assert_eq!(dapp.last_claim(bob), None);
+        }
+
+        #[ink::test]
+        fn faucet_fails_when_reserve_is_below_payout() {
+            let mut dapp = deploy_with_reserve(FAUCET_AMOUNT - 1);
+            let accounts = accounts();
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), accounts.bob), Err(Error::FaucetEmpty));
+            assert_eq!(dapp.balance_of(accounts.bob), 0);
+            assert_eq!(dapp.balance_of(accounts.alice), INITIAL_SUPPLY - (FAUCET_AMOUNT - 1));
+            assert_eq!(dapp.faucet_reserve(), FAUCET_AMOUNT - 1);
+            assert_eq!(dapp.last_claim(accounts.bob), None);
+            assert_eq!(dapp.claimed(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn faucet_message_fails_when_reserve_is_empty() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            // allowlisted accounts skip the humanity check, so no `Prosopo` contract is called
+            assert_eq!(dapp.add_to_list(AccessList::Allowlist, vec![accounts.bob]), Ok(()));
+            assert_eq!(dapp.faucet(accounts.bob), Err(Error::FaucetEmpty));
+            assert_eq!(dapp.balance_of(accounts.bob), 0);
+            assert_eq!(dapp.balance_of(accounts.alice), INITIAL_SUPPLY);
+            assert_eq!(dapp.faucet_reserve(), 0);
+            assert_eq!(dapp.last_claim(accounts.bob), None);
+            assert_eq!(dapp.claimed(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn faucet_enforces_the_cooldown() {
+            let mut dapp = deploy_with_reserve(1_000);
+            let bob = accounts().bob;
+            assert_eq!(dapp.set_faucet_cooldown(1_000), Ok(()));
+            test::set_block_timestamp::<DefaultEnvironment>(5_000);
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.last_claim(bob), Some(5_000));
+            test::set_block_timestamp::<DefaultEnvironment>(5_400);
+            assert_eq!(
+                dapp.faucet_with(&MockBackend::human(), bob),
+                Err(Error::FaucetCooldownActive { remaining_ms: 600 })
+            );
+            assert_eq!(dapp.balance_of(bob), FAUCET_AMOUNT);
+            test::set_block_timestamp::<DefaultEnvironment>(6_000);
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 2 * FAUCET_AMOUNT);
+        }
+
+        #[ink::test]
+        fn faucet_enforces_the_lifetime_cap() {
+            let mut dapp = deploy_with_reserve(1_000);
+            let bob = accounts().bob;
+            assert_eq!(dapp.set_faucet_lifetime_cap(Some(FAUCET_AMOUNT * 3 / 2)), Ok(()));
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Err(Error::FaucetCapReached));
+            assert_eq!(dapp.balance_of(bob), FAUCET_AMOUNT);
+            assert_eq!(dapp.claimed(bob), FAUCET_AMOUNT);
+        }
+
+        #[ink::test]
+        fn faucet_restricted_to_callers_accepts_relayers() {
+            let mut dapp = deploy_with_reserve(1_000);
+            let accounts = accounts();
+            assert_eq!(dapp.set_faucet_caller_only(true), Ok(()));
+            assert_eq!(dapp.set_relayer(accounts.charlie, true), Ok(()));
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), accounts.bob), Err(Error::FaucetCallerNotAllowed));
+            set_caller(accounts.bob);
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), accounts.bob), Ok(FaucetOutcome::Claimed));
+            set_caller(accounts.charlie);
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), accounts.django), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(accounts.bob), FAUCET_AMOUNT);
+            assert_eq!(dapp.balance_of(accounts.django), FAUCET_AMOUNT);
+            assert_eq!(dapp.balance_of(accounts.charlie), 0);
+            set_caller(accounts.alice);
+            assert_eq!(dapp.set_relayer(accounts.charlie, false), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), accounts.eve), Err(Error::FaucetCallerNotAllowed));
+        }
+
+        #[ink::test]
+        fn native_faucet_pays_out_of_the_reserve() {
+            let mut dapp = deploy_with_native_reserve(1_000);
+            let bob = accounts().bob;
+            let bob_balance = native_balance(bob);
+            assert_eq!(dapp.native_faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(native_balance(bob), bob_balance + NATIVE_FAUCET_AMOUNT);
+            assert_eq!(native_balance(contract_account()), 1_000 - NATIVE_FAUCET_AMOUNT);
+            assert_eq!(dapp.native_faucet_reserve(), 1_000 - NATIVE_FAUCET_AMOUNT);
+            assert_eq!(dapp.native_last_claim(bob), Some(0));
+            match recorded_events().last() {
+                Some(Event::NativeFaucetClaimed(claimed)) => {
+                    assert_eq!(claimed.account, bob);
+                    assert_eq!(claimed.amount, NATIVE_FAUCET_AMOUNT);
+                }
+                _ => panic!("expected a NativeFaucetClaimed event"),
+            }
+        }
+
+        #[ink::test]
+        fn native_faucet_enforces_its_cooldown() {
+            let mut dapp = deploy_with_native_reserve(1_000);
+            let bob = accounts().bob;
+            assert_eq!(dapp.set_native_faucet_cooldown(1_000), Ok(()));
+            assert_eq!(dapp.native_faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            test::set_block_timestamp::<DefaultEnvironment>(400);
+            assert_eq!(
+                dapp.native_faucet_with(&MockBackend::human(), bob),
+                Err(Error::FaucetCooldownActive { remaining_ms: 600 })
+            );
+            assert_eq!(dapp.native_faucet_reserve(), 1_000 - NATIVE_FAUCET_AMOUNT);
+        }
+
+        #[ink::test]
+        fn native_faucet_fails_when_reserve_is_empty() {
+            let mut dapp = deploy_with_native_reserve(NATIVE_FAUCET_AMOUNT - 1);
+            let bob = accounts().bob;
+            let bob_balance = native_balance(bob);
+            assert_eq!(dapp.native_faucet_with(&MockBackend::human(), bob), Err(Error::NativeFaucetEmpty));
+            assert_eq!(native_balance(bob), bob_balance);
+            assert_eq!(dapp.native_faucet_reserve(), NATIVE_FAUCET_AMOUNT - 1);
+            assert_eq!(dapp.native_last_claim(bob), None);
+        }
+
+        #[ink::test]
+        fn native_faucet_is_disabled_without_an_amount() {
+            let mut dapp = deploy();
+            assert_eq!(dapp.native_faucet_with(&MockBackend::human(), accounts().bob), Err(Error::NativeFaucetDisabled));
+        }
+
+        #[ink::test]
+        fn only_the_owner_withdraws_the_native_reserve() {
+            let mut dapp = deploy_with_native_reserve(1_000);
+            let accounts = accounts();
+            set_caller(accounts.bob);
+            assert_eq!(dapp.withdraw_native_faucet(300), Err(Error::NotOwner));
+            set_caller(accounts.alice);
+            let alice_balance = native_balance(accounts.alice);
+            assert_eq!(dapp.withdraw_native_faucet(300), Ok(()));
+            assert_eq!(native_balance(accounts.alice), alice_balance + 300);
+            assert_eq!(dapp.native_faucet_reserve(), 700);
+            assert_eq!(dapp.withdraw_native_faucet(701), Err(Error::NativeFaucetEmpty));
+            assert_eq!(dapp.native_faucet_reserve(), 700);
+        }
+
+        #[ink::test]
+        fn ensure_verified_reports_backend_answers() {
+            let dapp = deploy();
+            let bob = accounts().bob;
+            assert_eq!(
+                dapp.ensure_verified(&MockBackend::human(), bob, HUMAN_THRESHOLD, recency()),
+                Ok(Some(prosopo_account()))
+            );
+            assert_eq!(
+                dapp.ensure_verified(&MockBackend::not_human(), bob, HUMAN_THRESHOLD, recency()),
+                Err(Error::UserNotHuman)
+            );
+            assert_eq!(
+                dapp.ensure_verified(&MockBackend::stale(), bob, HUMAN_THRESHOLD, recency()),
+                Err(Error::CaptchaTooOld)
+            );
+            assert_eq!(
+                dapp.ensure_verified(&MockBackend::failing(), bob, HUMAN_THRESHOLD, recency()),
+                Err(Error::ProsopoCallFailed)
+            );
+        }
+
+        #[ink::test]
+        fn any_of_passes_with_one_passing_verifier() {
+            let mut dapp = deploy();
+            let bob = accounts().bob;
+            let allowlist = Verifier { contract: allowlist_account(), kind: VerifierKind::Allowlist, weight: 1 };
+            assert_eq!(dapp.add_verifier(allowlist), Ok(()));
+            let backend = MockBackend::not_human().with_verifier(allowlist_account(), Ok(true));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Ok(None));
+            let backend = MockBackend::human().with_verifier(allowlist_account(), Ok(false));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Ok(Some(prosopo_account())));
+            let backend = MockBackend::not_human().with_verifier(allowlist_account(), Ok(false));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Err(Error::UserNotHuman));
+        }
+
+        #[ink::test]
+        fn all_of_requires_every_verifier_to_pass() {
+            let mut dapp = deploy();
+            let bob = accounts().bob;
+            let allowlist = Verifier { contract: allowlist_account(), kind: VerifierKind::Allowlist, weight: 1 };
+            assert_eq!(dapp.add_verifier(allowlist), Ok(()));
+            assert_eq!(dapp.set_verification_policy(VerificationPolicy::AllOf), Ok(()));
+            let backend = MockBackend::human().with_verifier(allowlist_account(), Ok(true));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Ok(Some(prosopo_account())));
+            let backend = MockBackend::not_human().with_verifier(allowlist_account(), Ok(true));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Err(Error::UserNotHuman));
+            let backend = MockBackend::human().with_verifier(allowlist_account(), Ok(false));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Err(Error::UserNotHuman));
+            let backend = MockBackend::human();
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Err(Error::VerifierCallFailed));
+        }
+
+        #[ink::test]
+        fn weighted_policy_adds_up_passing_weights() {
+            let mut dapp = deploy();
+            let bob = accounts().bob;
+            let allowlist = Verifier { contract: allowlist_account(), kind: VerifierKind::Allowlist, weight: 2 };
+            let attestation = Verifier { contract: attestation_account(), kind: VerifierKind::Attestation, weight: 1 };
+            assert_eq!(dapp.add_verifier(allowlist), Ok(()));
+            assert_eq!(dapp.add_verifier(attestation), Ok(()));
+            assert_eq!(dapp.set_verification_policy(VerificationPolicy::Weighted { min_weight: 3 }), Ok(()));
+            let backend = MockBackend::human()
+                .with_verifier(allowlist_account(), Ok(true))
+                .with_verifier(attestation_account(), Ok(false));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Ok(Some(prosopo_account())));
+            let backend = MockBackend::not_human()
+                .with_verifier(allowlist_account(), Ok(true))
+                .with_verifier(attestation_account(), Ok(true));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Ok(None));
+            let backend = MockBackend::human()
+                .with_verifier(allowlist_account(), Ok(false))
+                .with_verifier(attestation_account(), Ok(true));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Err(Error::UserNotHuman));
+            let backend = MockBackend::not_human()
+                .with_verifier(allowlist_account(), Ok(true))
+                .with_verifier(attestation_account(), Err(Error::VerifierCallFailed));
+            assert_eq!(dapp.ensure_verified(&backend, bob, HUMAN_THRESHOLD, recency()), Err(Error::VerifierCallFailed));
+        }
+
+        #[ink::test]
+        fn empty_registry_fails_with_no_verifiers() {
+            let mut dapp = deploy();
+            assert_eq!(dapp.remove_verifier(prosopo_account()), Ok(()));
+            assert_eq!(dapp.verifiers(), Vec::new());
+            assert_eq!(
+                dapp.ensure_verified(&MockBackend::human(), accounts().bob, HUMAN_THRESHOLD, recency()),
+                Err(Error::NoVerifiers)
+            );
+        }
+
+        #[ink::test]
+        fn invalid_verifiers_and_policies_are_rejected() {
+            let mut dapp = deploy();
+            let weightless = Verifier { contract: allowlist_account(), kind: VerifierKind::Allowlist, weight: 0 };
+            assert_eq!(dapp.add_verifier(weightless), Err(Error::InvalidVerifier));
+            let duplicate = Verifier { contract: prosopo_account(), kind: VerifierKind::Allowlist, weight: 1 };
+            assert_eq!(dapp.add_verifier(duplicate), Err(Error::InvalidVerifier));
+            assert_eq!(dapp.verifiers().len(), 1);
+            assert_eq!(
+                dapp.set_verification_policy(VerificationPolicy::Weighted { min_weight: 2 }),
+                Err(Error::InvalidVerificationPolicy)
+            );
+            assert_eq!(dapp.verification_policy(), VerificationPolicy::AnyOf);
+            assert_eq!(dapp.set_verification_policy(VerificationPolicy::Weighted { min_weight: 1 }), Ok(()));
+            assert_eq!(dapp.remove_verifier(prosopo_account()), Err(Error::InvalidVerificationPolicy));
+            assert_eq!(dapp.verifiers().len(), 1);
+            let allowlist = Verifier { contract: allowlist_account(), kind: VerifierKind::Allowlist, weight: 1 };
+            assert_eq!(dapp.add_verifier(allowlist), Ok(()));
+            assert_eq!(dapp.set_prosopo_accounts(vec![allowlist_account()]), Err(Error::InvalidVerifier));
+        }
+
+        #[ink::test]
+        fn stale_captcha_skips_humanity_query() {
+            let backend = MockBackend::stale();
+            assert_eq!(
+                Dapp::ensure_human(&backend, prosopo_account(), accounts().bob, HUMAN_THRESHOLD, recency()),
+                Err(Error::CaptchaTooOld)
+            );
+            assert_eq!(backend.captcha_calls.get(), 1);
+            assert_eq!(backend.human_calls.get(), 0);
+        }
+
+        #[ink::test]
+        fn fresh_captcha_queries_prosopo_once_each() {
+            let backend = MockBackend::human();
+            assert_eq!(
+                Dapp::ensure_human(&backend, prosopo_account(), accounts().bob, HUMAN_THRESHOLD, recency()),
+                Ok(())
+            );
+            assert_eq!(backend.captcha_calls.get(), 1);
+            assert_eq!(backend.human_calls.get(), 1);
+        }
+
+        #[ink::test]
+        fn failed_prosopo_calls_fail_over_to_the_next_address() {
+            let mut dapp = deploy();
+            let fallback = AccountId::from([0xab; 32]);
+            assert_eq!(dapp.set_prosopo_accounts(vec![prosopo_account(), fallback]), Ok(()));
+            let mut backend = MockBackend::human();
+            backend.unreachable = Some(prosopo_account());
+            assert_eq!(
+                dapp.ensure_verified(&backend, accounts().bob, HUMAN_THRESHOLD, recency()),
+                Ok(Some(fallback))
+            );
+            assert_eq!(backend.captcha_calls.get(), 2);
+            assert_eq!(backend.human_calls.get(), 1);
+        }
+
+        #[ink::test]
+        fn prosopo_answers_do_not_fail_over() {
+            let mut dapp = deploy();
+            let fallback = AccountId::from([0xab; 32]);
+            assert_eq!(dapp.set_prosopo_accounts(vec![prosopo_account(), fallback]), Ok(()));
+            let backend = MockBackend::new(Err(Error::NoCaptchaHistory), Ok(100));
+            assert_eq!(
+                dapp.ensure_verified(&backend, accounts().bob, HUMAN_THRESHOLD, recency()),
+                Err(Error::NoCaptchaHistory)
+            );
+            assert_eq!(backend.captcha_calls.get(), 1);
+        }
+
+        #[ink::test]
+        fn set_prosopo_accounts_emits_registry_change() {
+            let mut dapp = deploy();
+            let primary = AccountId::from([0xab; 32]);
+            assert_eq!(dapp.set_prosopo_accounts(vec![primary, prosopo_account()]), Ok(()));
+            assert_eq!(dapp.prosopo_accounts(), vec![primary, prosopo_account()]);
+            let parameters: Vec<ConfigParameter> = recorded_events()
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::ConfigChanged(changed) => Some(changed.parameter),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(
+                parameters,
+                vec![
+                    ConfigParameter::Verifiers(vec![Verifier {
+                        contract: primary,
+                        kind: VerifierKind::Prosopo,
+                        weight: 1,
+                    }]),
+                    ConfigParameter::ProsopoAccounts(vec![primary, prosopo_account()]),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn large_transfer_check_passes_verifier_errors_through() {
+            let mut dapp = deploy();
+            let alice = accounts().alice;
+            assert_eq!(dapp.set_large_transfer_threshold(Some(10)), Ok(()));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::human(), alice, 11), Ok(()));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::not_human(), alice, 11), Err(Error::UserNotHuman));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::stale(), alice, 11), Err(Error::UserNotHuman));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::no_history(), alice, 11), Err(Error::UserNotHuman));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::failing(), alice, 11), Err(Error::ProsopoCallFailed));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::failing(), alice, 10), Ok(()));
+            assert_eq!(dapp.remove_verifier(prosopo_account()), Ok(()));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::human(), alice, 11), Err(Error::NoVerifiers));
+        }
+
+        #[ink::test]
+        fn access_lists_do_not_affect_large_transfers() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            assert_eq!(dapp.set_large_transfer_threshold(Some(10)), Ok(()));
+            assert_eq!(dapp.add_to_list(AccessList::Allowlist, vec![accounts.alice]), Ok(()));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::not_human(), accounts.alice, 11), Err(Error::UserNotHuman));
+            assert_eq!(dapp.add_to_list(AccessList::Denylist, vec![accounts.bob]), Ok(()));
+            assert_eq!(dapp.ensure_transfer_allowed(&MockBackend::human(), accounts.bob, 11), Ok(()));
+        }
+
+        #[ink::test]
+        fn raising_the_threshold_invalidates_cached_checks() {
+            let mut dapp = deploy();
+            let bob = accounts().bob;
+            assert_eq!(dapp.set_verification_cache_ttl(Some(60_000)), Ok(()));
+            assert_eq!(dapp.ensure_human_cached(&MockBackend::human(), bob), Ok(Some(prosopo_account())));
+            let backend = MockBackend::not_human();
+            assert_eq!(dapp.ensure_human_cached(&backend, bob), Ok(None));
+            assert_eq!(backend.captcha_calls.get(), 0);
+            assert_eq!(dapp.set_human_threshold(HUMAN_THRESHOLD + 1), Ok(()));
+            assert_eq!(dapp.ensure_human_cached(&backend, bob), Err(Error::UserNotHuman));
+            assert_eq!(backend.captcha_calls.get(), 1);
+        }
+
+        #[ink::test]
+        fn denylisting_drops_cached_checks() {
+            let mut dapp = deploy();
+            let bob = accounts().bob;
+            assert_eq!(dapp.set_verification_cache_ttl(Some(60_000)), Ok(()));
+            assert_eq!(dapp.ensure_human_cached(&MockBackend::human(), bob), Ok(Some(prosopo_account())));
+            assert_eq!(dapp.add_to_list(AccessList::Denylist, vec![bob]), Ok(()));
+            assert_eq!(dapp.verified_at(bob), None);
+            assert_eq!(dapp.ensure_human_cached(&MockBackend::not_human(), bob), Err(Error::UserNotHuman));
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::HumanCheckFailed(Error::Denylisted)));
+        }
+
+        #[ink::test]
+        fn tiers_at_or_below_the_human_threshold_skip_lookups() {
+            let mut dapp = deploy_with_reserve(10_000);
+            let bob = accounts().bob;
+            let tiers = vec![FaucetTier { threshold: HUMAN_THRESHOLD - 10, amount: 200 }];
+            assert_eq!(dapp.set_faucet_tiers(tiers), Ok(()));
+            let backend = MockBackend::human();
+            assert_eq!(dapp.faucet_with(&backend, bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 200);
+            assert_eq!(backend.human_calls.get(), 1);
+        }
+
+        #[ink::test]
+        fn cached_checks_keep_their_tier() {
+            let mut dapp = deploy_with_reserve(10_000);
+            let bob = accounts().bob;
+            let tiers = vec![
+                FaucetTier { threshold: HUMAN_THRESHOLD - 10, amount: 200 },
+                FaucetTier { threshold: HUMAN_THRESHOLD + 10, amount: 500 },
+            ];
+            assert_eq!(dapp.set_faucet_tiers(tiers), Ok(()));
+            assert_eq!(dapp.set_verification_cache_ttl(Some(60_000)), Ok(()));
+            let backend = MockBackend::human();
+            assert_eq!(dapp.faucet_with(&backend, bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 500);
+            assert_eq!(backend.human_calls.get(), 2);
+            let backend = MockBackend::human();
+            assert_eq!(dapp.faucet_with(&backend, bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 1_000);
+            assert_eq!(backend.captcha_calls.get(), 0);
+            assert_eq!(backend.human_calls.get(), 1);
+            let backend = MockBackend::new(Ok(1_000), Ok(HUMAN_THRESHOLD));
+            assert_eq!(dapp.faucet_with(&backend, bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 1_200);
+            assert_eq!(backend.human_calls.get(), 1);
+        }
+
+        #[ink::test]
+        fn allowlisted_accounts_get_their_tier() {
+            let mut dapp = deploy_with_reserve(10_000);
+            let bob = accounts().bob;
+            let tiers = vec![
+                FaucetTier { threshold: HUMAN_THRESHOLD - 10, amount: 200 },
+                FaucetTier { threshold: HUMAN_THRESHOLD + 10, amount: 500 },
+            ];
+            assert_eq!(dapp.set_faucet_tiers(tiers), Ok(()));
+            assert_eq!(dapp.add_to_list(AccessList::Allowlist, vec![bob]), Ok(()));
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 500);
+            assert_eq!(dapp.faucet_with(&MockBackend::new(Ok(1_000), Ok(HUMAN_THRESHOLD - 10)), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 700);
+            assert_eq!(dapp.faucet_with(&MockBackend::failing(), bob), Ok(FaucetOutcome::Claimed));
+            assert_eq!(dapp.balance_of(bob), 700 + FAUCET_AMOUNT);
+        }
+
+        #[ink::test]
+        fn constructor_emits_transfer_event() {
+            deploy();
+            let events = recorded_events();
+            assert_eq!(events.len(), 1);
+            assert_transfer_event(&events[0], None, Some(accounts().alice), INITIAL_SUPPLY);
+        }
+
+        #[ink::test]
+        fn transfer_emits_transfer_event() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            assert_eq!(dapp.transfer(accounts.bob, 10), Ok(()));
+            let events = recorded_events();
+            assert_eq!(events.len(), 2);
+            assert_transfer_event(&events[1], Some(accounts.alice), Some(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn transfer_from_emits_transfer_and_approval_events() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            assert_eq!(dapp.approve(accounts.bob, 15), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(dapp.transfer_from(accounts.alice, accounts.charlie, 10, Vec::new()), Ok(()));
+            let events = recorded_events();
+            assert_eq!(events.len(), 4);
+            assert_transfer_event(&events[2], Some(accounts.alice), Some(accounts.charlie), 10);
+            match &events[3] {
+                Event::Approval(approval) => {
+                    assert_eq!(approval.owner, accounts.alice);
+                    assert_eq!(approval.spender, accounts.bob);
+                    assert_eq!(approval.value, 5);
+                }
+                _ => panic!("expected an Approval event"),
+            }
+        }
+
+        #[ink::test]
+        fn faucet_emits_faucet_claimed_event() {
+            let mut dapp = deploy_with_reserve(1_000);
+            let bob = accounts().bob;
+            assert_eq!(dapp.faucet_with(&MockBackend::human(), bob), Ok(FaucetOutcome::Claimed));
+            let events = recorded_events();
+            assert_transfer_event(&events[events.len() - 2], Some(contract_account()), Some(bob), FAUCET_AMOUNT);
+            match events.last() {
+                Some(Event::FaucetClaimed(claimed)) => {
+                    assert_eq!(claimed.account, bob);
+                    assert_eq!(claimed.amount, FAUCET_AMOUNT);
+                    assert_eq!(claimed.threshold_used, HUMAN_THRESHOLD);
+                    assert_eq!(claimed.tier, None);
+                    assert_eq!(claimed.prosopo_instance, Some(prosopo_account()));
+                }
+                _ => panic!("expected a FaucetClaimed event"),
+            }
+        }
+
+        #[ink::test]
+        fn faucet_emits_human_check_failed_event() {
+            let mut dapp = deploy_with_reserve(1_000);
+            let bob = accounts().bob;
+            assert_eq!(
+                dapp.faucet_with(&MockBackend::not_human(), bob),
+                Ok(FaucetOutcome::HumanCheckFailed(Error::UserNotHuman))
+            );
+            match recorded_events().last() {
+                Some(Event::HumanCheckFailed(failed)) => {
+                    assert_eq!(failed.account, bob);
+                    assert_eq!(failed.reason, Error::UserNotHuman);
+                }
+                _ => panic!("expected a HumanCheckFailed event"),
+            }
+        }
+
+        #[ink::test]
+        fn transfer_moves_tokens() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            assert_eq!(dapp.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(dapp.balance_of(accounts.alice), INITIAL_SUPPLY - 10);
+            assert_eq!(dapp.balance_of(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn transfer_fails_without_enough_balance() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            set_caller(accounts.bob);
+            assert_eq!(dapp.transfer(accounts.charlie, 1), Err(Error::InsufficientBalance));
+            assert_eq!(dapp.balance_of(accounts.bob), 0);
+            assert_eq!(dapp.balance_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn random_transfers_mints_and_burns_conserve_supply() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            let backend = MockBackend::human();
+            let holders = [accounts.alice, accounts.bob, accounts.charlie, accounts.django, contract_account()];
+            let mut rng = Rng(0x5eed_1234_abcd_ef01);
+            for _ in 0..1_000 {
+                let caller = holders[rng.below(holders.len() as u64) as usize];
+                let to = holders[rng.below(holders.len() as u64) as usize];
+                // amounts up to twice the average holding so that some calls fail
+                let value = Balance::from(rng.below(2 * INITIAL_SUPPLY as u64 / holders.len() as u64));
+                let result = match rng.below(3) {
+                    0 => {
+                        set_caller(caller);
+                        dapp.transfer(to, value)
+                    }
+                    1 => {
+                        set_caller(accounts.alice);
+                        dapp.mint(to, value)
+                    }
+                    _ => {
+                        set_caller(caller);
+                        dapp.burn_with(&backend, value)
+                    }
+                };
+                assert!(matches!(result, Ok(()) | Err(Error::InsufficientBalance)));
+                let total: Balance = holders.iter().map(|holder| dapp.balance_of(*holder)).sum();
+                assert_eq!(total, dapp.total_supply());
+            }
+        }
+
+        #[ink::test]
+        fn burn_destroys_tokens_of_humans() {
+            let mut dapp = deploy();
+            let alice = accounts().alice;
+            assert_eq!(dapp.burn_with(&MockBackend::human(), 10), Ok(()));
+            assert_eq!(dapp.balance_of(alice), INITIAL_SUPPLY - 10);
+            assert_eq!(dapp.total_supply(), INITIAL_SUPPLY - 10);
+            assert_transfer_event(recorded_events().last().unwrap(), Some(alice), None, 10);
+        }
+
+        #[ink::test]
+        fn burn_requires_the_humanity_check() {
+            let mut dapp = deploy();
+            let alice = accounts().alice;
+            assert_eq!(dapp.burn_with(&MockBackend::not_human(), 10), Err(Error::UserNotHuman));
+            assert_eq!(dapp.burn_with(&MockBackend::stale(), 10), Err(Error::UserNotHuman));
+            assert_eq!(dapp.burn_with(&MockBackend::failing(), 10), Err(Error::ProsopoCallFailed));
+            assert_eq!(dapp.balance_of(alice), INITIAL_SUPPLY);
+            assert_eq!(dapp.total_supply(), INITIAL_SUPPLY);
+        }
+
+        #[ink::test]
+        fn mint_overflows_at_balance_max() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            assert_eq!(dapp.mint(accounts.bob, Balance::MAX), Err(Error::Overflow));
+            assert_eq!(dapp.mint(accounts.alice, Balance::MAX - INITIAL_SUPPLY + 1), Err(Error::Overflow));
+            assert_eq!(dapp.balance_of(accounts.alice), INITIAL_SUPPLY);
+            assert_eq!(dapp.balance_of(accounts.bob), 0);
+            assert_eq!(dapp.total_supply(), INITIAL_SUPPLY);
+        }
+
+        #[ink::test]
+        fn increase_allowance_overflows_at_balance_max() {
+            let mut dapp = deploy();
+            let bob = accounts().bob;
+            assert_eq!(dapp.approve(bob, Balance::MAX), Ok(()));
+            assert_eq!(dapp.increase_allowance(bob, 1), Err(Error::Overflow));
+            assert_eq!(dapp.allowance(accounts().alice, bob), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn pausing_transfers_pauses_burn_from() {
+            let mut dapp = deploy();
+            let accounts = accounts();
+            assert_eq!(dapp.transfer(accounts.bob, 10), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(dapp.approve(accounts.alice, 10), Ok(()));
+            set_caller(accounts.alice);
+            assert_eq!(dapp.pause(PauseTarget::Transfers), Ok(()));
+            assert_eq!(dapp.burn_from(accounts.bob, 10), Err(Error::Paused));
+            assert_eq!(dapp.unpause(PauseTarget::Transfers), Ok(()));
+            assert_eq!(dapp.burn_from(accounts.bob, 10), Ok(()));
+            assert_eq!(dapp.total_supply(), INITIAL_SUPPLY - 10);
+        }
+
+        #[ink::test]
+        fn migrate_seeds_the_verifier_registry_from_version_1() {
+            let mut dapp = deploy();
+            dapp.storage_version.set(&1);
+            dapp.verifiers.set(&Vec::new());
+            assert_eq!(dapp.migrate(), Ok(()));
+            assert_eq!(
+                dapp.verifiers(),
+                vec![Verifier { contract: prosopo_account(), kind: VerifierKind::Prosopo, weight: 1 }]
+            );
+            assert_eq!(dapp.verification_policy(), VerificationPolicy::AnyOf);
+            assert_eq!(dapp.storage_version(), STORAGE_VERSION);
+            match recorded_events().last() {
+                Some(Event::Migrated(migrated)) => {
+                    assert_eq!(migrated.from_version, 1);
+                    assert_eq!(migrated.to_version, STORAGE_VERSION);
+                }
+                _ => panic!("expected a Migrated event"),
+            }
+            assert_eq!(dapp.migrate(), Err(Error::NothingToMigrate));
+        }
+
+        #[ink::test]
+        fn fresh_deployments_have_nothing_to_migrate() {
+            let mut dapp = deploy();
+            assert_eq!(dapp.storage_version(), STORAGE_VERSION);
+            assert_eq!(dapp.migrate(), Err(Error::NothingToMigrate));
+        }
+
+        #[ink::test]
+        fn only_the_owner_upgrades_and_migrates() {
+            let mut dapp = deploy();
+            dapp.storage_version.set(&1);
+            set_caller(accounts().bob);
+            assert_eq!(dapp.upgrade(Hash::from([0x01; 32])), Err(Error::NotOwner));
+            assert_eq!(dapp.migrate(), Err(Error::NotOwner));
+            assert_eq!(dapp.storage_version(), 1);
+        }
+
+        #[ink::test]
+        fn balance_of_defaults_to_zero() {
+            let dapp = deploy();
+            let accounts = accounts();
+            assert_eq!(dapp.balance_of(accounts.alice), INITIAL_SUPPLY);
+            assert_eq!(dapp.balance_of(accounts.bob), 0);
         }
     }
 }
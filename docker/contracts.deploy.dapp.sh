@@ -6,7 +6,7 @@ DEPLOY_RESULT=$(/usr/src/docker/contracts.deploy.contract.sh \
   --contract-source="$DAPP_CONTRACT_SOURCE" \
   --wasm="$DAPP_CONTRACT_WASM" \
   --constructor="$DAPP_CONTRACT_CONSTRUCTOR" \
-  --contract-args="$DAPP_CONTRACT_ARGS_INITIAL_SUPPLY $DAPP_CONTRACT_ARGS_FAUCET_AMOUNT $CONTRACT_ADDRESS $DAPP_CONTRACT_ARGS_HUMAN_THRESHOLD $DAPP_CONTRACT_ARGS_RECENCY_THRESHOLD" \
+  --contract-args="$DAPP_CONTRACT_ARGS_INITIAL_SUPPLY $DAPP_CONTRACT_ARGS_FAUCET_AMOUNT $CONTRACT_ADDRESS $DAPP_CONTRACT_ARGS_HUMAN_THRESHOLD $DAPP_CONTRACT_ARGS_RECENCY_POLICY $DAPP_CONTRACT_ARGS_TOKEN_NAME $DAPP_CONTRACT_ARGS_TOKEN_SYMBOL $DAPP_CONTRACT_ARGS_TOKEN_DECIMALS $DAPP_CONTRACT_ARGS_FAUCET_COOLDOWN $DAPP_CONTRACT_ARGS_FAUCET_LIFETIME_CAP $DAPP_CONTRACT_ARGS_FAUCET_CALLER_ONLY $DAPP_CONTRACT_ARGS_RELAYERS" \
   --endowment="$DAPP_CONTRACT_ENDOWMENT" \
   --endpoint="$SUBSTRATE_ENDPOINT" \
   --port="$SUBSTRATE_PORT" \